@@ -3,13 +3,30 @@
 //
 // 순수 파싱만 담당 (IO 없음, 테스트 용이)
 
+use minisftp_core::sftp::{ModeSpec, Owner};
+
 pub enum Command {
     // 리모트 명령
     Ls { path: String },
-    Get { remote: String, local: String },
-    Put { local: String, remote: String },
+    /// keep_links: -l (심볼릭 링크를 로컬 링크로 보존, 없으면 대상 내용을 받음)
+    /// preserve: -p (권한도 복사, 시각은 항상 복사)
+    Get { remote: String, local: String, keep_links: bool, preserve: bool },
+    Put { local: String, remote: String, preserve: bool },
     Mkdir { path: String },
-    Rm { path: String },
+    /// recursive: -r (디렉토리 트리), force: -f (확인 없이)
+    Rm { path: String, recursive: bool, force: bool },
+    Rmdir { path: String },
+    /// mv / rename
+    Rename { from: String, to: String },
+    /// chmod [-R] <mode> <path>
+    Chmod { mode: ModeSpec, path: String, recursive: bool },
+    /// chown [-R] <owner[:group]> / chgrp [-R] <group> (숫자 id 또는 이름, 이름은 서버에서 id로 변환)
+    Chown { user: Option<Owner>, group: Option<Owner>, path: String, recursive: bool },
+    /// touch [-R] <path> (-R: 이미 있는 트리 전체의 시각을 현재로)
+    Touch { path: String, recursive: bool },
+    Stat { path: String },
+    /// ln [-s] <target> <link> / symlink <target> <link>
+    Link { target: String, link: String, symbolic: bool },
     Pwd,
     Cd { path: String },
     // 로컬 명령 (!ls, !cd, !pwd)
@@ -17,6 +34,8 @@ pub enum Command {
     LocalLs { path: String },
     LocalCd { path: String },
     LocalPwd,
+    // 서버 정보 (SSH 버전, 알고리즘, SFTP 버전 / 확장)
+    Info,
     Help,
     Quit,
     Unknown(String),
@@ -24,7 +43,7 @@ pub enum Command {
 
 impl Command {
     pub fn parse(input: &str) -> Self {
-        let parts: Vec<&str> = input.trim().split_whitespace().collect();
+        let parts: Vec<&str> = input.split_whitespace().collect();
 
         if parts.is_empty() {
             return Command::Unknown(String::new());
@@ -63,24 +82,26 @@ impl Command {
                 path: parts.get(1).unwrap_or(&".").to_string(),
             },
             "get" => {
-                if parts.len() < 2 {
-                    return Command::Unknown("Usage: get <remote> [local]".to_string());
+                let (flags, args) = leading_flags(&parts[1..]);
+                if args.is_empty() || flags.chars().any(|c| c != 'l' && c != 'p') {
+                    return Command::Unknown("Usage: get [-l] [-p] <remote> [local]".to_string());
                 }
-                let remote = parts[1].to_string();
-                let local = parts.get(2)
+                let remote = args[0].to_string();
+                let local = args.get(1)
                     .map(|s| s.to_string())
                     .unwrap_or_else(|| extract_filename(&remote));
-                Command::Get { remote, local }
+                Command::Get { remote, local, keep_links: flags.contains('l'), preserve: flags.contains('p') }
             }
             "put" => {
-                if parts.len() < 2 {
-                    return Command::Unknown("Usage: put <local> [remote]".to_string());
+                let (flags, args) = leading_flags(&parts[1..]);
+                if args.is_empty() || flags.chars().any(|c| c != 'p') {
+                    return Command::Unknown("Usage: put [-p] <local> [remote]".to_string());
                 }
-                let local = parts[1].to_string();
-                let remote = parts.get(2)
+                let local = args[0].to_string();
+                let remote = args.get(1)
                     .map(|s| s.to_string())
                     .unwrap_or_else(|| extract_filename(&local));
-                Command::Put { local, remote }
+                Command::Put { local, remote, preserve: flags.contains('p') }
             }
             "mkdir" => {
                 if parts.len() < 2 {
@@ -89,15 +110,100 @@ impl Command {
                 Command::Mkdir { path: parts[1].to_string() }
             }
             "rm" => {
+                // rm [-r] [-f] <path> (-rf, -fr도 허용)
+                let (flags, args): (Vec<&str>, Vec<&str>) = parts[1..].iter()
+                    .partition(|p| p.len() > 1 && p.starts_with('-'));
+                let flags: String = flags.concat().replace('-', "");
+                if args.len() != 1 || flags.chars().any(|c| c != 'r' && c != 'f') {
+                    return Command::Unknown("Usage: rm [-r] [-f] <path>".to_string());
+                }
+                Command::Rm {
+                    path: args[0].to_string(),
+                    recursive: flags.contains('r'),
+                    force: flags.contains('f'),
+                }
+            }
+            "rmdir" => {
+                if parts.len() < 2 {
+                    return Command::Unknown("Usage: rmdir <path>".to_string());
+                }
+                Command::Rmdir { path: parts[1].to_string() }
+            }
+            "mv" | "rename" => {
+                if parts.len() < 3 {
+                    return Command::Unknown(format!("Usage: {} <from> <to>", parts[0]));
+                }
+                Command::Rename { from: parts[1].to_string(), to: parts[2].to_string() }
+            }
+            "chmod" | "chown" | "chgrp" => {
+                // -R은 첫 인자로만 (chmod -w 같은 기호 모드와 구분)
+                let recursive = parts.get(1) == Some(&"-R");
+                let args = &parts[if recursive { 2 } else { 1 }..];
+                let usage = match parts[0] {
+                    "chmod" => "Usage: chmod [-R] <mode> <path>",
+                    "chown" => "Usage: chown [-R] <owner[:group]> <path>",
+                    _       => "Usage: chgrp [-R] <group> <path>",
+                };
+                if args.len() != 2 {
+                    return Command::Unknown(usage.to_string());
+                }
+                let path = args[1].to_string();
+                // 빈 값은 변경 안 함
+                let id = |s: &str| -> Result<Option<Owner>, String> {
+                    if s.is_empty() {
+                        return Ok(None);
+                    }
+                    s.parse().map(Some).map_err(|_| format!("Invalid owner: {}", s))
+                };
+                match parts[0] {
+                    "chmod" => match args[0].parse() {
+                        Ok(mode) => Command::Chmod { mode, path, recursive },
+                        Err(_)   => Command::Unknown(format!("Invalid mode: {}", args[0])),
+                    },
+                    "chown" => {
+                        let (owner, group) = args[0].split_once(':').unwrap_or((args[0], ""));
+                        match (id(owner), id(group)) {
+                            (Ok(None), Ok(None))  => Command::Unknown(usage.to_string()),
+                            (Ok(user), Ok(group)) => Command::Chown { user, group, path, recursive },
+                            (Err(e), _) | (_, Err(e))  => Command::Unknown(e),
+                        }
+                    }
+                    _ => match id(args[0]) {
+                        Ok(None)  => Command::Unknown(usage.to_string()),
+                        Ok(group) => Command::Chown { user: None, group, path, recursive },
+                        Err(e)    => Command::Unknown(e),
+                    },
+                }
+            }
+            "ln" | "symlink" => {
+                let symbolic = parts[0] == "symlink" || parts.get(1) == Some(&"-s");
+                let args = &parts[if parts.get(1) == Some(&"-s") { 2 } else { 1 }..];
+                if args.len() != 2 {
+                    return Command::Unknown(match parts[0] {
+                        "ln" => "Usage: ln [-s] <target> <link>".to_string(),
+                        _    => "Usage: symlink <target> <link>".to_string(),
+                    });
+                }
+                Command::Link { target: args[0].to_string(), link: args[1].to_string(), symbolic }
+            }
+            "stat" => {
                 if parts.len() < 2 {
-                    return Command::Unknown("Usage: rm <path>".to_string());
+                    return Command::Unknown("Usage: stat <path>".to_string());
+                }
+                Command::Stat { path: parts[1].to_string() }
+            }
+            "touch" => {
+                let recursive = parts.get(1) == Some(&"-R");
+                match parts.get(if recursive { 2 } else { 1 }) {
+                    Some(path) => Command::Touch { path: path.to_string(), recursive },
+                    None       => Command::Unknown("Usage: touch [-R] <path>".to_string()),
                 }
-                Command::Rm { path: parts[1].to_string() }
             }
             "pwd"           => Command::Pwd,
             "cd"            => Command::Cd {
                 path: parts.get(1).unwrap_or(&"~").to_string(),
             },
+            "info"          => Command::Info,
             "help" | "?"    => Command::Help,
             "quit" | "exit" => Command::Quit,
             other => Command::Unknown(format!("Unknown command: {}", other)),
@@ -105,6 +211,12 @@ impl Command {
     }
 }
 
+/// 앞쪽 옵션 인자 분리 (["-l", "-p", "a"] / ["-lp", "a"] → ("lp", ["a"]))
+fn leading_flags<'a>(args: &'a [&'a str]) -> (String, &'a [&'a str]) {
+    let count = args.iter().take_while(|a| a.len() > 1 && a.starts_with('-')).count();
+    (args[..count].concat().replace('-', ""), &args[count..])
+}
+
 /// 경로에서 파일명만 추출 ("/remote/path/file.txt" → "file.txt")
 fn extract_filename(path: &str) -> String {
     path.rsplit('/')
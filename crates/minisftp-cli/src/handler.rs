@@ -5,14 +5,20 @@
 // Tauri 전환 시 이 로직을 거의 그대로 가져갈 수 있음
 
 use std::io::{self, Write};
+use std::path::PathBuf;
+use std::sync::Arc;
 
-use minisftp_core::config::{AuthMethod, ConnectConfig};
-use minisftp_core::sftp::{CancellationToken, ProgressInfo, TransferResult};
+use minisftp_core::auth::{KeyboardInteractivePrompter, Prompt};
+use minisftp_core::config::ConnectConfig;
+use minisftp_core::known_hosts::{HostKeyInfo, HostKeyPrompter};
+use minisftp_core::secret::Secret;
+use minisftp_core::sftp::{CancellationToken, ProgressInfo, RemoveProgress, RemoveResult, SymlinkMode, TransferOptions, TransferResult, WalkResult};
 use minisftp_core::state::{ConnectionObserver, ConnectionState};
 use minisftp_core::session::SftpSession;
 use minisftp_core::utils::{fmt_size, local_ls, print_progress, resolve_path, resolve_local_path};
 
 use crate::commands::Command;
+use crate::tty;
 
 struct CliObserver;
 
@@ -20,36 +26,128 @@ impl ConnectionObserver for CliObserver {
     fn on_state_changed(&self, _prev: &ConnectionState, next: &ConnectionState) {
         println!("[state] → {:?}", next);
     }
+
+    fn on_hop_state_changed(&self, hop: usize, total: usize, addr: &str, state: &ConnectionState) {
+        println!("[jump {}/{} {}] → {:?}", hop, total, addr, state);
+    }
+
+    /// 배너는 서버가 보낸 그대로 출력 (OpenSSH와 같이 stderr)
+    fn on_banner(&self, _addr: &str, banner: &str) {
+        eprint!("{}", banner);
+        if !banner.ends_with('\n') {
+            eprintln!();
+        }
+    }
+}
+
+/// keyboard-interactive: 서버 질문을 터미널에서 입력받음 (echo=false 질문은 화면에 표시하지 않음)
+struct CliPrompter;
+
+impl KeyboardInteractivePrompter for CliPrompter {
+    fn prompt(&self, name: &str, instructions: &str, prompts: &[Prompt]) -> Option<Vec<Secret>> {
+        if !name.is_empty()         { eprintln!("{}", name); }
+        if !instructions.is_empty() { eprintln!("{}", instructions); }
+
+        let mut answers = Vec::with_capacity(prompts.len());
+        for p in prompts {
+            eprint!("{}", p.prompt);
+            // EOF(^D) → 취소
+            let answer = tty::read_line(p.echo)?;
+            answers.push(Secret::from(answer.as_str()));
+        }
+        Some(answers)
+    }
 }
 
+/// 처음 보는 호스트키: OpenSSH와 같은 형식으로 fingerprint 출력 후 yes/no 확인
+struct CliHostKeyPrompter;
+
+impl HostKeyPrompter for CliHostKeyPrompter {
+    fn confirm(&self, info: &HostKeyInfo) -> bool {
+        eprintln!("The authenticity of host '{}' can't be established.",
+            if info.port == 22 { info.host.clone() } else { format!("[{}]:{}", info.host, info.port) });
+        eprintln!("{} key fingerprint is {}.", info.key_type, info.fingerprint);
+        loop {
+            eprint!("Are you sure you want to continue connecting (yes/no)? ");
+            let mut answer = String::new();
+            if io::stdin().read_line(&mut answer).unwrap_or(0) == 0 {
+                return false;
+            }
+            match answer.trim() {
+                "yes" => return true,
+                "no"  => return false,
+                _     => eprintln!("Please type 'yes' or 'no'."),
+            }
+        }
+    }
+}
+
+/// remote_dir / local_dir: 시작 디렉토리 (프로필 설정, None이면 홈 / 현재 디렉토리)
 pub async fn run(
-    host: String,
-    port: u16,
-    username: String,
-    password: String,
+    config: ConnectConfig,
+    remote_dir: Option<String>,
+    local_dir: Option<PathBuf>,
 ) -> minisftp_core::error::Result<()> {
-    let config = ConnectConfig { host, port, username, auth: AuthMethod::Password(password) };
-
     println!("Connecting to {}:{}...", config.host, config.port);
 
     let mut session = SftpSession::new(Box::new(CliObserver));
+    session.set_prompter(Box::new(CliPrompter));
+    session.set_host_key_prompter(Arc::new(CliHostKeyPrompter));
     let mut sftp    = session.connect(&config).await?;
 
-    // 방식 A: 접속 직후 서버에 실제 홈 디렉토리 확인
-    let mut remote_dir = sftp.realpath(".").await
-        .unwrap_or_else(|_| ".".to_string());
+    // 방식 A: 접속 직후 서버에 실제 홈 디렉토리 확인 (시작 디렉토리가 없으면 홈 사용)
+    let start = match remote_dir {
+        Some(dir) => match sftp.realpath(&dir).await {
+            Ok(dir) => Some(dir),
+            Err(e) => {
+                println!("Start directory unavailable: {}", e);
+                None
+            }
+        },
+        None => None,
+    };
+    let mut remote_dir = match start {
+        Some(dir) => dir,
+        None => sftp.realpath(".").await.unwrap_or_else(|_| ".".to_string()),
+    };
 
     println!("Connected. Remote: {}  Type 'help' for commands.", remote_dir);
-    let mut local_dir  = std::env::current_dir()
+    let mut local_dir  = local_dir
+        .filter(|dir| dir.is_dir())
+        .or_else(|| std::env::current_dir().ok())
         .map(|p| p.to_string_lossy().to_string())
-        .unwrap_or_else(|_| ".".to_string());
+        .unwrap_or_else(|| ".".to_string());
 
     loop {
         print!("sftp> ");
         io::stdout().flush().unwrap();
 
+        // EOF(^D) → quit
         let mut input = String::new();
-        io::stdin().read_line(&mut input).unwrap();
+        if io::stdin().read_line(&mut input).unwrap() == 0 {
+            input = "quit".to_string();
+        }
+
+        // 백그라운드 감시로 끊김이 감지됐으면 (observer가 이미 상태 출력) 재연결 시도
+        // 재연결 성공 → 새 클라이언트 + 작업 디렉토리 복원 후 입력한 명령 계속 실행
+        if !session.is_connected() {
+            if config.reconnect.max_attempts == 0 || matches!(Command::parse(&input), Command::Quit) {
+                println!("Connection closed.");
+                break;
+            }
+            println!("Connection lost. Reconnecting...");
+            match session.reconnect(Some(&remote_dir)).await {
+                Ok((client, dir)) => {
+                    sftp = client;
+                    remote_dir = dir;
+                    println!("Reconnected. Remote: {}", remote_dir);
+                }
+                Err(e) => {
+                    println!("Reconnect failed: {}", e);
+                    break;
+                }
+            }
+        }
 
         match Command::parse(&input) {
             // ── 리모트 명령 ──────────────────────────────────────
@@ -64,7 +162,7 @@ pub async fn run(
                                 e.uid.map(|u| u.to_string()).unwrap_or_else(|| "?".to_string()),
                                 e.gid.map(|g| g.to_string()).unwrap_or_else(|| "?".to_string()),
                                 e.mtime_str(),
-                                e.name,
+                                e.display_name(),
                             );
                         }
                         println!("Total: {} entries", entries.len());
@@ -83,18 +181,15 @@ pub async fn run(
                     Err(e)   => println!("Error: {}", e),
                 }
             }
-            Command::Get { remote, local } => {
+            Command::Get { remote, local, keep_links, preserve } => {
                 let remote_path = resolve_path(&remote_dir, &remote);
                 let local_path  = resolve_local_path(&local_dir, &local);
-                let token       = CancellationToken::new();
-                // ^C 태스크: 신호 수신 시 token.cancel()만 호출
-                // get()은 다음 청크에서 Cancelled(n)을 반환하고 정상 종료
-                let token_clone = token.clone();
-                tokio::spawn(async move {
-                    let _ = tokio::signal::ctrl_c().await;
-                    token_clone.cancel();
-                });
-                match sftp.get(&remote_path, &local_path,
+                let token       = ctrl_c_token();
+                let options = TransferOptions {
+                    symlinks: if keep_links { SymlinkMode::Preserve } else { SymlinkMode::Follow },
+                    preserve_mode: preserve,
+                };
+                match sftp.get(&remote_path, &local_path, options,
                     |p: ProgressInfo| print_progress(p.transferred, p.total, p.elapsed_secs),
                     token,
                 ).await {
@@ -102,19 +197,16 @@ pub async fn run(
                     Ok(TransferResult::Resumed(n))   => { println!(); println!("Resumed: {} → {} ({} total)", remote_path, local_path, fmt_size(n)); }
                     Ok(TransferResult::Completed(n)) => { println!(); println!("Downloaded: {} → {} ({})", remote_path, local_path, fmt_size(n)); }
                     Ok(TransferResult::Cancelled(n)) => { println!(); println!("Cancelled. ({} transferred)", fmt_size(n)); }
+                    Ok(TransferResult::Linked(t))    => println!("Linked: {} -> {}", local_path, t),
                     Err(e) => { println!(); println!("Error: {}", e); }
                 }
             }
-            Command::Put { local, remote } => {
+            Command::Put { local, remote, preserve } => {
                 let local_path  = resolve_local_path(&local_dir, &local);
                 let remote_path = resolve_path(&remote_dir, &remote);
-                let token       = CancellationToken::new();
-                let token_clone = token.clone();
-                tokio::spawn(async move {
-                    let _ = tokio::signal::ctrl_c().await;
-                    token_clone.cancel();
-                });
-                match sftp.put(&local_path, &remote_path,
+                let token       = ctrl_c_token();
+                let options = TransferOptions { preserve_mode: preserve, ..TransferOptions::default() };
+                match sftp.put(&local_path, &remote_path, options,
                     |p: ProgressInfo| print_progress(p.transferred, p.total, p.elapsed_secs),
                     token,
                 ).await {
@@ -122,6 +214,7 @@ pub async fn run(
                     Ok(TransferResult::Resumed(n))   => { println!(); println!("Resumed: {} → {} ({} total)", local_path, remote_path, fmt_size(n)); }
                     Ok(TransferResult::Completed(n)) => { println!(); println!("Uploaded: {} → {} ({})", local_path, remote_path, fmt_size(n)); }
                     Ok(TransferResult::Cancelled(n)) => { println!(); println!("Cancelled. ({} transferred)", fmt_size(n)); }
+                    Ok(TransferResult::Linked(_))    => {}
                     Err(e) => { println!(); println!("Error: {}", e); }
                 }
             }
@@ -132,13 +225,125 @@ pub async fn run(
                     Err(e) => println!("Error: {}", e),
                 }
             }
-            Command::Rm { path } => {
+            Command::Rm { path, recursive: false, .. } => {
                 let target = resolve_path(&remote_dir, &path);
                 match sftp.rm(&target).await {
                     Ok(()) => println!("Removed: {}", target),
                     Err(e) => println!("Error: {}", e),
                 }
             }
+            Command::Rm { path, recursive: true, force } => {
+                let target = resolve_path(&remote_dir, &path);
+                if !force && !confirm(&format!("Remove '{}' and everything under it?", target)) {
+                    println!("Aborted.");
+                    continue;
+                }
+                let token = ctrl_c_token();
+                match sftp.remove_all(&target,
+                    |p: &RemoveProgress| {
+                        print!("\rRemoving... {} files, {} dirs", p.files, p.dirs);
+                        let _ = io::stdout().flush();
+                    },
+                    token,
+                ).await {
+                    Ok(RemoveResult::Completed { files, dirs }) => { println!(); println!("Removed: {} ({} files, {} dirs)", target, files, dirs); }
+                    Ok(RemoveResult::Cancelled { files, dirs }) => { println!(); println!("Cancelled. ({} files, {} dirs removed)", files, dirs); }
+                    Err(e) => { println!(); println!("Error: {}", e); }
+                }
+            }
+            Command::Rmdir { path } => {
+                let target = resolve_path(&remote_dir, &path);
+                match sftp.rmdir(&target).await {
+                    Ok(()) => println!("Removed: {}", target),
+                    Err(e) => println!("Error: {}", e),
+                }
+            }
+            Command::Rename { from, to } => {
+                let from = resolve_path(&remote_dir, &from);
+                let mut to = resolve_path(&remote_dir, &to);
+                // 대상이 디렉토리면 그 안으로 이동 (mv와 같음)
+                if sftp.is_dir(&to).await.unwrap_or(false) {
+                    let name = from.rsplit('/').next().unwrap_or(&from);
+                    to = resolve_path(&to, name);
+                }
+                match sftp.rename(&from, &to).await {
+                    Ok(()) => println!("Renamed: {} → {}", from, to),
+                    Err(e) => println!("Error: {}", e),
+                }
+            }
+            Command::Chmod { mode, path, recursive: false } => {
+                let target = resolve_path(&remote_dir, &path);
+                match sftp.chmod(&target, &mode).await {
+                    Ok(mode) => println!("Mode {:04o}: {}", mode, target),
+                    Err(e)   => println!("Error: {}", e),
+                }
+            }
+            Command::Chmod { mode, path, recursive: true } => {
+                let target = resolve_path(&remote_dir, &path);
+                let token  = ctrl_c_token();
+                print_walk_result(sftp.chmod_recursive(&target, &mode, token).await, &target);
+            }
+            Command::Chown { user, group, path, recursive } => {
+                let target = resolve_path(&remote_dir, &path);
+                if recursive {
+                    let token = ctrl_c_token();
+                    print_walk_result(sftp.chown_recursive(&target, user.as_ref(), group.as_ref(), token).await, &target);
+                } else {
+                    match sftp.chown(&target, user.as_ref(), group.as_ref()).await {
+                        Ok(()) => println!("Owner changed: {}", target),
+                        Err(e) => println!("Error: {}", e),
+                    }
+                }
+            }
+            Command::Link { target, link, symbolic } => {
+                let link = resolve_path(&remote_dir, &link);
+                let result = if symbolic {
+                    // 심볼릭 링크 대상은 입력 그대로 저장 (상대경로는 링크 위치 기준)
+                    sftp.symlink(&target, &link).await
+                } else {
+                    sftp.hardlink(&resolve_path(&remote_dir, &target), &link).await
+                };
+                match result {
+                    Ok(()) if symbolic => println!("Linked: {} -> {}", link, target),
+                    Ok(())             => println!("Hard linked: {} = {}", link, target),
+                    Err(e)             => println!("Error: {}", e),
+                }
+            }
+            Command::Stat { path } => {
+                let target = resolve_path(&remote_dir, &path);
+                match sftp.lstat(&target).await {
+                    Ok(e) => {
+                        println!("  File: {}", e.display_name());
+                        match e.target_type {
+                            Some(t) => println!("  Type: {:?} -> {:?}", e.file_type, t),
+                            None    => println!("  Type: {:?}", e.file_type),
+                        }
+                        println!("  Size: {} ({})", e.size, fmt_size(e.size));
+                        println!("  Mode: {}  {}", e.mode().map(|m| format!("{:04o}", m)).unwrap_or_else(|| "?".to_string()), e.permission_str());
+                        println!(" Owner: uid={}  gid={}",
+                            e.uid.map(|u| u.to_string()).unwrap_or_else(|| "?".to_string()),
+                            e.gid.map(|g| g.to_string()).unwrap_or_else(|| "?".to_string()));
+                        println!("Access: {}", e.atime_str());
+                        println!("Modify: {}", e.mtime_str());
+                        for (name, data) in &e.extended {
+                            println!("   Ext: {} = {}", name, data);
+                        }
+                    }
+                    Err(e) => println!("Error: {}", e),
+                }
+            }
+            Command::Touch { path, recursive: true } => {
+                let target = resolve_path(&remote_dir, &path);
+                let token  = ctrl_c_token();
+                print_walk_result(sftp.touch_recursive(&target, token).await, &target);
+            }
+            Command::Touch { path, recursive: false } => {
+                let target = resolve_path(&remote_dir, &path);
+                match sftp.touch(&target).await {
+                    Ok(()) => println!("Touched: {}", target),
+                    Err(e) => println!("Error: {}", e),
+                }
+            }
 
             // ── 로컬 명령 (!ls, !cd, !pwd) ───────────────────────
             Command::LocalLs { path } => {
@@ -165,13 +370,58 @@ pub async fn run(
             Command::LocalPwd => println!("Local: {}", local_dir),
 
             // ── 기타 ─────────────────────────────────────────────
+            Command::Info => match session.server_info() {
+                Some(info) => {
+                    println!("Server:      {}", info.ssh_version);
+                    println!("Host key:    {} {}", info.host_key_type, info.fingerprint);
+                    if let Some(a) = &info.algorithms {
+                        println!("Kex:         {}", a.kex);
+                        println!("Cipher:      {}", a.cipher);
+                        println!("MAC:         {}", a.mac);
+                        println!("Compression: {}", a.compression);
+                    }
+                    println!("SFTP:        version {}", info.sftp_version);
+                    if info.extensions.is_empty() {
+                        println!("Extensions:  (none)");
+                    }
+                    for (i, (name, data)) in info.extensions.iter().enumerate() {
+                        println!("{}{} ({})", if i == 0 { "Extensions:  " } else { "             " }, name, data);
+                    }
+                    if let Some(banner) = &info.banner {
+                        println!("Banner:");
+                        for line in banner.lines() {
+                            println!("  {}", line);
+                        }
+                    }
+                }
+                None => println!("Not connected."),
+            },
             Command::Help => {
                 println!("Remote commands:");
                 println!("  ls [path]             List remote directory");
-                println!("  get <remote> [local]  Download file");
-                println!("  put <local> [remote]  Upload file");
+                println!("  get [-l] [-p] <remote> [local]");
+                println!("                        Download file (-l: keep a symlink as a local symlink,");
+                println!("                        -p: also copy permissions; times are always copied)");
+                println!("  put [-p] <local> [remote]");
+                println!("                        Upload file (-p: also copy permissions)");
                 println!("  mkdir <path>          Create remote directory");
                 println!("  rm <path>             Remove remote file");
+                println!("  rm -r [-f] <path>     Remove remote directory tree (asks first unless -f)");
+                println!("  rmdir <path>          Remove empty remote directory");
+                println!("  mv <from> <to>        Rename / move (replaces the target if the server");
+                println!("                        supports posix-rename; alias: rename)");
+                println!("  chmod [-R] <mode> <path>");
+                println!("                        Change permissions (755, u+x, go-w, a=rX)");
+                println!("  chown [-R] <owner[:group]> <path>");
+                println!("                        Change owner / group (id or name)");
+                println!("  chgrp [-R] <group> <path>");
+                println!("                        Change group (id or name)");
+                println!("  stat <path>           Show type, size, mode, owner and times (symlink itself)");
+                println!("  touch [-R] <path>     Create empty file or update its times (-R: whole tree)");
+                println!("  ln [-s] <target> <link>");
+                println!("                        Create hard link (hardlink@openssh.com) or symlink (-s)");
+                println!("  symlink <target> <link>");
+                println!("                        Create symlink");
                 println!("  cd <path>             Change remote directory");
                 println!("  pwd                   Show remote directory");
                 println!();
@@ -180,10 +430,13 @@ pub async fn run(
                 println!("  !cd <path>            Change local directory");
                 println!("  !pwd                  Show local directory");
                 println!();
+                println!("  info                  Show server version, algorithms and SFTP extensions");
                 println!("  help                  Show this help");
                 println!("  quit                  Exit");
             }
             Command::Quit => {
+                drop(sftp);
+                session.disconnect().await?;
                 println!("Goodbye.");
                 break;
             }
@@ -195,6 +448,46 @@ pub async fn run(
     Ok(())
 }
 
+/// ^C 수신 시 취소되는 토큰 (전송 / 재귀 작업용)
+///
+/// ^C 태스크는 신호 수신 시 token.cancel()만 호출
+/// get() / put() 등은 다음 청크에서 Cancelled(n)을 반환하고 정상 종료
+fn ctrl_c_token() -> CancellationToken {
+    let token       = CancellationToken::new();
+    let token_clone = token.clone();
+    tokio::spawn(async move {
+        let _ = tokio::signal::ctrl_c().await;
+        token_clone.cancel();
+    });
+    token
+}
+
+/// chmod -R / chown -R / touch -R 결과 출력
+fn print_walk_result(result: minisftp_core::error::Result<WalkResult>, target: &str) {
+    match result {
+        Ok(WalkResult::Completed(n)) => println!("Changed: {} ({} entries)", target, n),
+        Ok(WalkResult::Cancelled(n)) => println!("Cancelled. ({} entries changed)", n),
+        Err(e) => println!("Error: {}", e),
+    }
+}
+
+/// yes/no 확인 (EOF면 no)
+fn confirm(question: &str) -> bool {
+    loop {
+        print!("{} (yes/no) ", question);
+        io::stdout().flush().unwrap();
+        let mut answer = String::new();
+        if io::stdin().read_line(&mut answer).unwrap_or(0) == 0 {
+            return false;
+        }
+        match answer.trim() {
+            "yes" | "y" => return true,
+            "no" | "n"  => return false,
+            _           => println!("Please type 'yes' or 'no'."),
+        }
+    }
+}
+
 /// 로컈 ls 출력: 플랫폼별 포맷
 ///
 /// Windows: dir 스타일 (2026-02-21  오후 01:35    <DIR>  .cargo)
@@ -220,7 +513,11 @@ fn print_local_ls(entries: &[minisftp_core::sftp::FileEntry]) {
                 })
                 .unwrap_or_else(|| "                      ".to_string());
 
-            if e.is_dir {
+            if e.is_symlink() {
+                let label = if e.is_dir() { "<SYMLINKD>" } else { "<SYMLINK>" };
+                let target = e.link_target.as_deref().unwrap_or("?");
+                println!("{:22}    {:<14}  {} [{}]", dt_str, label, e.name, target);
+            } else if e.is_dir() {
                 println!("{:22}    {:<14}  {}", dt_str, "<DIR>", e.name);
             } else {
                 println!("{:22}    {:>14}  {}", dt_str, fmt_comma(e.size), e.name);
@@ -236,7 +533,7 @@ fn print_local_ls(entries: &[minisftp_core::sftp::FileEntry]) {
             e.uid.map(|u| u.to_string()).unwrap_or_else(|| "?".to_string()),
             e.gid.map(|g| g.to_string()).unwrap_or_else(|| "?".to_string()),
             e.mtime_str(),
-            e.name,
+            e.display_name(),
         );
     }
 
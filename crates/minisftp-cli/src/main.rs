@@ -1,18 +1,38 @@
 // miniSFTP CLI
 // author: kodeholic (powered by Claude)
 //
-// Usage: minisftp user@host [port]
+// Usage: minisftp [-F ssh_config] [-J [user@]jump[:port],...] [--proxy=url | --proxy-command=cmd] [-i identity_file] [--agent[=fingerprint|comment]] [--keyboard-interactive]
+//                 [--password] [--strict-host-key-checking=yes|accept-new|ask|no] [--no-reconnect]
+//                 [--connect-timeout=secs] [--keepalive=secs] [-c ciphers] [-m macs] [--kex=list]
+//                 [--host-key-algorithms=list] [-C] [user@]host [port] | @profile [port]
+//        minisftp --list-profiles | --import-filezilla=sitemanager.xml
+//        minisftp --vault-init | --vault-passwd | --vault-list | --vault-save=profile | --vault-remove=id
 
 use std::env;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use minisftp_core::algorithms::{self, AlgorithmKind};
+use minisftp_core::auth::load_private_key;
+use minisftp_core::config::{AuthMethod, HostKeyPolicy, ProxyConfig, ReconnectPolicy};
+use minisftp_core::error::Error;
+use minisftp_core::profile::{Profile, ProfileAuth, ProfileStore};
+use minisftp_core::secret::{self, Secret};
+use minisftp_core::ssh_config;
+use minisftp_core::vault::{KdfParams, Vault};
+use tracing_subscriber::filter::filter_fn;
+use tracing_subscriber::prelude::*;
 use tracing_subscriber::{EnvFilter, fmt};
 
 mod commands;
 mod handler;
+mod tty;
 
 #[tokio::main]
 async fn main() {
     // RUST_LOG=debug cargo run -p minisftp-cli -- user@host
-    // RUST_LOG=trace cargo run -p minisftp-cli -- user@host  (hex dump 포함)
+    // RUST_LOG=trace cargo run -p minisftp-cli -- user@host
+    // russh 평문 패킷 로그(패스워드 포함)는 RUST_LOG와 무관하게 차단
     fmt()
         .with_env_filter(
             EnvFilter::try_from_default_env()
@@ -22,34 +42,321 @@ async fn main() {
         .with_thread_ids(false)
         .with_file(false)
         .with_line_number(false)
+        .finish()
+        .with(filter_fn(|meta| !secret::is_sensitive_log(meta.target(), meta.level())))
         .init();
 
-    let args: Vec<String> = env::args().collect();
+    // 옵션(-F, -i, --agent, --keyboard-interactive, --password)과 위치 인자([user@]host [port]) 분리
+    let mut config_file: Option<PathBuf> = None;
+    let mut proxy_jump: Option<String> = None;
+    let mut proxy: Option<ProxyConfig> = None;
+    let mut identity: Option<PathBuf> = None;
+    let mut keyboard_interactive = false;
+    let mut password = false;
+    let mut reconnect = true;
+    let mut connect_timeout: Option<u64> = None;
+    // 0이면 keepalive 끔
+    let mut keepalive: Option<u64> = None;
+    // 알고리즘 목록 (OpenSSH 표기: "a,b" / "+a" / "-a*" / "^a")
+    let mut ciphers: Option<String> = None;
+    let mut macs: Option<String> = None;
+    let mut kex: Option<String> = None;
+    let mut host_key_algorithms: Option<String> = None;
+    let mut compression = false;
+    let mut host_key_policy: Option<HostKeyPolicy> = None;
+    // Some(None): agent 전체 키, Some(Some(s)): fingerprint/comment로 선택
+    let mut agent: Option<Option<String>> = None;
+    let mut positional: Vec<String> = Vec::new();
+    let mut args = env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "-F" => match args.next() {
+                Some(path) => config_file = Some(PathBuf::from(path)),
+                None => {
+                    print_usage();
+                    return;
+                }
+            },
+            "-J" => match args.next() {
+                Some(spec) => proxy_jump = Some(spec),
+                None => {
+                    print_usage();
+                    return;
+                }
+            },
+            "-i" => match args.next() {
+                Some(path) => identity = Some(PathBuf::from(path)),
+                None => {
+                    print_usage();
+                    return;
+                }
+            },
+            "-c" | "-m" => match args.next() {
+                Some(spec) if arg == "-c" => ciphers = Some(spec),
+                Some(spec) => macs = Some(spec),
+                None => {
+                    print_usage();
+                    return;
+                }
+            },
+            "-C" => compression = true,
+            "--agent" => agent = Some(None),
+            "--keyboard-interactive" => keyboard_interactive = true,
+            "--password" => password = true,
+            "--no-reconnect" => reconnect = false,
+            "--list-profiles" => {
+                list_profiles();
+                return;
+            }
+            "--vault-init" => {
+                vault_init();
+                return;
+            }
+            "--vault-passwd" => {
+                vault_passwd();
+                return;
+            }
+            "--vault-list" => {
+                vault_list();
+                return;
+            }
+            _ if arg.starts_with("--vault-save=") => {
+                vault_save(arg["--vault-save=".len()..].trim_start_matches('@'));
+                return;
+            }
+            _ if arg.starts_with("--vault-remove=") => {
+                vault_remove(&arg["--vault-remove=".len()..]);
+                return;
+            }
+            _ if arg.starts_with("--import-filezilla=") => {
+                import_filezilla(Path::new(&arg["--import-filezilla=".len()..]));
+                return;
+            }
+            _ if arg.starts_with("--strict-host-key-checking=") => {
+                let value = &arg["--strict-host-key-checking=".len()..];
+                host_key_policy = match HostKeyPolicy::from_openssh(value) {
+                    Some(policy) => Some(policy),
+                    None => {
+                        println!("Invalid --strict-host-key-checking value: {}", value);
+                        return;
+                    }
+                };
+            }
+            _ if arg.starts_with("--connect-timeout=") => {
+                match arg["--connect-timeout=".len()..].parse::<u64>() {
+                    Ok(secs) if secs > 0 => connect_timeout = Some(secs),
+                    _ => {
+                        println!("Invalid --connect-timeout value: {}", &arg["--connect-timeout=".len()..]);
+                        return;
+                    }
+                }
+            }
+            _ if arg.starts_with("--keepalive=") => {
+                match arg["--keepalive=".len()..].parse::<u64>() {
+                    Ok(secs) => keepalive = Some(secs),
+                    Err(_) => {
+                        println!("Invalid --keepalive value: {}", &arg["--keepalive=".len()..]);
+                        return;
+                    }
+                }
+            }
+            _ if arg.starts_with("--kex=") => kex = Some(arg["--kex=".len()..].to_string()),
+            _ if arg.starts_with("--host-key-algorithms=") => {
+                host_key_algorithms = Some(arg["--host-key-algorithms=".len()..].to_string());
+            }
+            _ if arg.starts_with("--proxy=") => {
+                let url = &arg["--proxy=".len()..];
+                match ProxyConfig::from_url(url) {
+                    Some(p) => proxy = Some(p),
+                    None => {
+                        println!("Invalid --proxy value: {} (socks5://[user[:pass]@]host[:port] or http://...)", url);
+                        return;
+                    }
+                }
+            }
+            _ if arg.starts_with("--proxy-command=") => {
+                proxy = Some(ProxyConfig::Command(arg["--proxy-command=".len()..].to_string()));
+            }
+            _ if arg.starts_with("--agent=") => {
+                agent = Some(arg.strip_prefix("--agent=").map(|s| s.to_string()));
+            }
+            _ => positional.push(arg),
+        }
+    }
 
-    if args.len() < 2 {
+    if positional.is_empty() {
         print_usage();
         return;
     }
 
-    // user@host 파싱
-    let target = &args[1];
-    let port = if args.len() >= 3 {
-        args[2].parse::<u16>().unwrap_or(22)
+    // @name → 저장된 프로필, [user@]host → host는 ~/.ssh/config 별칭으로 해석
+    // 명령줄의 user / port / 옵션이 프로필 / 설정 파일보다 우선
+    let target = &positional[0];
+    let mut start_dirs = (None, None);
+    let mut config = if let Some(name) = target.strip_prefix('@') {
+        let profile = match ProfileStore::open_default() {
+            Ok(store) => store.get(name).cloned(),
+            Err(e) => {
+                println!("Error: {}", e);
+                return;
+            }
+        };
+        let Some(profile) = profile else {
+            println!("Error: no such profile: {} (see --list-profiles)", name);
+            return;
+        };
+        // 볼트에 저장된 패스워드 / passphrase 사용, 없으면 아래에서 입력받음
+        let config = match unlock_vault_for(&profile) {
+            Some(mut vault) => profile.to_connect_config_with_vault(&mut vault),
+            None => profile.to_connect_config(),
+        };
+        match config {
+            Ok(config) => {
+                password |= profile.uses_password()
+                    && !config.auth_methods.iter().any(|m| matches!(m, AuthMethod::Password(_)));
+                start_dirs = (profile.remote_dir, profile.local_dir);
+                config
+            }
+            Err(e) => {
+                println!("Error: {}", e);
+                return;
+            }
+        }
     } else {
-        22
+        let (cli_user, alias) = match target.split_once('@') {
+            Some((u, h)) => (Some(u.to_string()), h.to_string()),
+            None => (None, target.clone()),
+        };
+
+        let resolved = match &config_file {
+            Some(path) if !path.exists() => {
+                println!("Error: ssh config not found: {}", path.display());
+                return;
+            }
+            Some(path) => ssh_config::resolve(path, &alias),
+            None => ssh_config::resolve_default(&alias),
+        };
+        let mut host_config = match resolved {
+            Ok(host_config) => host_config,
+            Err(e) => {
+                println!("Error: ssh config: {}", e);
+                return;
+            }
+        };
+        // 명령줄 user@host가 User보다 우선
+        if let Some(user) = cli_user {
+            host_config.user = Some(user);
+        }
+        match host_config.to_connect_config(&alias) {
+            Ok(config) => config,
+            Err(e) => {
+                println!("Error: {}", e);
+                return;
+            }
+        }
     };
 
-    let (username, host) = match target.split_once('@') {
-        Some((u, h)) => (u.to_string(), h.to_string()),
-        None => {
-            println!("Format: minisftp user@host [port]");
+    if let Some(port) = positional.get(1) {
+        match port.parse::<u16>() {
+            Ok(port) => config.port = port,
+            Err(_) => {
+                println!("Invalid port: {}", port);
+                return;
+            }
+        }
+    }
+    if let Some(policy) = host_key_policy {
+        config.host_key_policy = policy;
+    }
+    if !reconnect {
+        config.reconnect = ReconnectPolicy::disabled();
+    }
+    if let Some(secs) = connect_timeout {
+        config.timeouts.tcp_connect = Some(Duration::from_secs(secs));
+    }
+    match keepalive {
+        Some(0) => config.keepalive.interval = None,
+        Some(secs) => config.keepalive.interval = Some(Duration::from_secs(secs)),
+        None => {}
+    }
+    if let Some(spec) = ciphers {
+        config.algorithms.cipher = algorithms::parse_spec(AlgorithmKind::Cipher, &spec);
+    }
+    if let Some(spec) = macs {
+        config.algorithms.mac = algorithms::parse_spec(AlgorithmKind::Mac, &spec);
+    }
+    if let Some(spec) = kex {
+        config.algorithms.kex = algorithms::parse_spec(AlgorithmKind::Kex, &spec);
+    }
+    if let Some(spec) = host_key_algorithms {
+        config.algorithms.host_key = algorithms::parse_spec(AlgorithmKind::HostKey, &spec);
+    }
+    if compression {
+        config.algorithms.compression = vec!["zlib@openssh.com".into(), "zlib".into(), "none".into()];
+    }
+    if let Some(mut proxy) = proxy {
+        // URL에 사용자만 있고 패스워드가 없으면 입력받음
+        if let ProxyConfig::Socks5 { host, credentials: Some(creds), .. }
+             | ProxyConfig::HttpConnect { host, credentials: Some(creds), .. } = &mut proxy {
+            if creds.password.is_empty() {
+                creds.password = read_proxy_password(&creds.username, host);
+            }
+        }
+        config.proxy = Some(proxy);
+    }
+    if let Some(spec) = proxy_jump {
+        // -J 의 hop도 ssh config 별칭으로 해석
+        let path = config_file.clone().or_else(ssh_config::default_path).unwrap_or_default();
+        config.jump_hosts = match ssh_config::parse_proxy_jump(&path, &spec) {
+            Ok(hops) => hops,
+            Err(e) => {
+                println!("Error: ssh config: {}", e);
+                return;
+            }
+        };
+    }
+
+    // 인증 순서: agent → 키 파일 → keyboard-interactive → 패스워드
+    // 인증 옵션을 주면 그것만 사용, 없으면 ssh config(agent, IdentityFile) 사용
+    // 사용할 방법이 하나도 없으면 패스워드
+    let cli_identity = identity.clone();
+    if agent.is_some() || identity.is_some() || keyboard_interactive {
+        config.auth_methods.clear();
+        if let Some(identity) = agent {
+            config.auth_methods.push(AuthMethod::Agent { identity });
+        }
+        if let Some(path) = identity {
+            config.auth_methods.push(AuthMethod::PublicKey { private_key_path: path, passphrase: None });
+        }
+        if keyboard_interactive {
+            config.auth_methods.push(AuthMethod::KeyboardInteractive { submethods: None });
+        }
+    }
+
+    // 암호화된 키 passphrase / 패스워드 입력 (점프 호스트 → 대상 서버 순서)
+    // 점프 호스트는 인증 방법이 하나도 없을 때만 패스워드
+    for jump in config.jump_hosts.iter_mut() {
+        if let Err(e) = prepare_auth(&mut jump.auth_methods, None) {
+            println!("Error: {}", e);
             return;
         }
-    };
+        if jump.auth_methods.is_empty() {
+            let password = read_password(&jump.username, &jump.host);
+            jump.auth_methods.push(AuthMethod::Password(password));
+        }
+    }
 
-    let password = read_password(&username, &host);
+    if let Err(e) = prepare_auth(&mut config.auth_methods, cli_identity.as_deref()) {
+        println!("Error: {}", e);
+        return;
+    }
+    if password || config.auth_methods.is_empty() {
+        let password = read_password(&config.username, &config.host);
+        config.auth_methods.push(AuthMethod::Password(password));
+    }
 
-    if let Err(e) = handler::run(host, port, username, password).await {
+    let (remote_dir, local_dir) = start_dirs;
+    if let Err(e) = handler::run(config, remote_dir, local_dir).await {
         println!("Error: {}", e);
     }
 }
@@ -57,16 +364,294 @@ async fn main() {
 fn print_usage() {
     println!("miniSFTP - SSH File Transfer Client");
     println!();
-    println!("Usage: minisftp user@host [port]");
+    println!("Usage: minisftp [options] [user@]host [port]");
+    println!("       minisftp [options] @profile [port]");
+    println!("       minisftp --list-profiles | --import-filezilla=<sitemanager.xml>");
+    println!();
+    println!("Options:");
+    println!("  -F <file>      SSH config file (default: ~/.ssh/config)");
+    println!("  -J <jumps>     Jump hosts, comma separated: [user@]host[:port],...");
+    println!("  --proxy=<url>  Connect through a proxy: socks5://[user[:pass]@]host[:port]");
+    println!("                 or http://[user[:pass]@]host[:port] (HTTP CONNECT)");
+    println!("  --proxy-command=<cmd>");
+    println!("                 Run cmd and speak SSH over its stdin/stdout (%h %p %r expanded)");
+    println!("  --no-reconnect Do not reconnect automatically when the connection drops");
+    println!("  --connect-timeout=<secs>");
+    println!("                 TCP connect timeout (default: 15, ssh config: ConnectTimeout)");
+    println!("  --keepalive=<secs>");
+    println!("                 Keepalive interval, 0 disables (default: 30, ssh config: ServerAliveInterval)");
+    println!("  -c <ciphers>   Cipher preference, OpenSSH syntax: list, +append, -remove, ^prepend");
+    println!("  -m <macs>      MAC preference (same syntax)");
+    println!("  --kex=<list>   Key exchange preference (same syntax)");
+    println!("  --host-key-algorithms=<list>");
+    println!("                 Host key algorithm preference (same syntax)");
+    println!("  -C             Request compression (zlib@openssh.com, zlib)");
+    println!("  -i <file>      Private key file (OpenSSH / PEM: ed25519, ECDSA, RSA)");
+    println!("  --agent        Authenticate with keys from ssh-agent (SSH_AUTH_SOCK)");
+    println!("  --agent=<id>   Use only the agent key matching fingerprint or comment");
+    println!("  --keyboard-interactive");
+    println!("                 Answer server prompts (PAM, OTP) on the terminal");
+    println!("  --password     Also try password (default when no other option is given)");
+    println!("  --strict-host-key-checking=<yes|accept-new|ask|no>");
+    println!("                 Host key policy against ~/.ssh/known_hosts (default: ask)");
+    println!("  --list-profiles");
+    println!("                 List saved connection profiles");
+    println!("  --import-filezilla=<file>");
+    println!("                 Import SFTP sites from a FileZilla sitemanager.xml into the profiles");
+    println!("  --vault-init   Create the encrypted credential vault (master password)");
+    println!("  --vault-passwd Change the vault master password");
+    println!("  --vault-save=<profile>");
+    println!("                 Store the profile's password / key passphrase in the vault");
+    println!("  --vault-list   List secrets stored in the vault (names only)");
+    println!("  --vault-remove=<id>");
+    println!("                 Remove a secret from the vault");
+    println!();
+    if let Some(path) = ProfileStore::default_path() {
+        println!("Profiles are stored in {}.", path.display());
+    }
+    println!("When the vault exists, @profile asks for the master password and uses stored secrets.");
+    println!("host may be a Host alias from ~/.ssh/config (HostName, Port, User, IdentityFile, ...).");
+    println!("Command line user, port and options override the config file.");
+    println!("Methods are tried in order: agent, key file, keyboard-interactive, password.");
     println!();
     println!("Example:");
     println!("  minisftp admin@192.168.1.100");
     println!("  minisftp admin@192.168.1.100 2222");
+    println!("  minisftp myserver                  (Host alias in ~/.ssh/config)");
+    println!("  minisftp @prod-web                 (saved profile)");
+    println!("  minisftp -J admin@bastion admin@10.0.0.5");
+    println!("  minisftp --proxy=socks5://proxy.corp:1080 admin@partner.example.com");
+    println!("  minisftp --proxy-command='nc -X connect -x proxy:3128 %h %p' admin@partner.example.com");
+    println!("  minisftp --kex=+diffie-hellman-group1-sha1 -c aes128-cbc admin@legacy-switch");
+    println!("  minisftp -i ~/.ssh/id_ed25519 admin@192.168.1.100");
+    println!("  minisftp --agent=SHA256:abc... admin@192.168.1.100");
+    println!("  minisftp -i ~/.ssh/id_ed25519 --password admin@192.168.1.100");
+}
+
+/// 저장된 프로필 출력 (폴더별, 태그 포함)
+fn list_profiles() {
+    let store = match ProfileStore::open_default() {
+        Ok(store) => store,
+        Err(e) => {
+            println!("Error: {}", e);
+            return;
+        }
+    };
+    if store.profiles().is_empty() {
+        println!("No profiles in {}", store.path().display());
+        return;
+    }
+
+    let mut profiles: Vec<_> = store.profiles().iter().collect();
+    profiles.sort_by(|a, b| a.folder.cmp(&b.folder).then(a.name.cmp(&b.name)));
+    let mut folder = None;
+    for p in profiles {
+        if p.folder.as_ref() != folder {
+            folder = p.folder.as_ref();
+            println!("{}/", folder.map(String::as_str).unwrap_or(""));
+        }
+        let user = p.username.as_deref().map(|u| format!("{}@", u)).unwrap_or_default();
+        let port = p.port.map(|port| format!(":{}", port)).unwrap_or_default();
+        let tags = if p.tags.is_empty() { String::new() } else { format!("  [{}]", p.tags.join(", ")) };
+        println!("  @{:20} {}{}{}{}", p.name, user, p.host, port, tags);
+    }
+}
+
+/// FileZilla 사이트 관리자 가져오기 → 프로필 저장
+fn import_filezilla(path: &Path) {
+    let result = ProfileStore::open_default().and_then(|mut store| {
+        let report = store.import_filezilla(path)?;
+        store.save()?;
+        Ok((report, store.path().to_path_buf()))
+    });
+    match result {
+        Ok((report, saved)) => {
+            for name in &report.imported {
+                println!("Imported @{}", name);
+            }
+            for skipped in &report.skipped {
+                println!("Skipped {}", skipped);
+            }
+            println!("{} imported, {} skipped → {}", report.imported.len(), report.skipped.len(), saved.display());
+            if !report.imported.is_empty() {
+                println!("Saved passwords are not imported; you will be asked when connecting.");
+            }
+        }
+        Err(e) => println!("Error: {}", e),
+    }
+}
+
+/// @profile 접속용 볼트 잠금 해제 (볼트가 없거나, 저장할 비밀이 없거나, 빈 입력이면 None)
+fn unlock_vault_for(profile: &Profile) -> Option<Vault> {
+    if !profile.auth.iter().any(|auth| profile.secret_id(auth).is_some()) {
+        return None;
+    }
+    let path = Vault::default_path().filter(|path| path.exists())?;
+    let mut vault = match Vault::open(path) {
+        Ok(vault) => vault,
+        Err(e) => {
+            println!("Error: {}", e);
+            return None;
+        }
+    };
+    let master = read_secret("Vault master password (empty to skip): ");
+    if master.is_empty() {
+        return None;
+    }
+    match vault.unlock(&master) {
+        Ok(()) => Some(vault),
+        Err(e) => {
+            println!("Error: {}", e);
+            None
+        }
+    }
+}
+
+/// 볼트 열고 마스터 패스워드로 잠금 해제
+fn unlock_vault() -> Result<Vault, Error> {
+    let mut vault = Vault::open_default()?;
+    vault.unlock(&read_secret("Vault master password: "))?;
+    Ok(vault)
+}
+
+/// 새 마스터 패스워드 두 번 입력 (불일치면 None)
+fn read_new_master() -> Option<Secret> {
+    let master = read_secret("New vault master password: ");
+    if read_secret("Retype new vault master password: ").expose() != master.expose() {
+        println!("Passwords do not match.");
+        return None;
+    }
+    Some(master)
+}
+
+fn vault_init() {
+    let Some(path) = Vault::default_path() else {
+        println!("Error: cannot locate config directory");
+        return;
+    };
+    if path.exists() {
+        println!("Vault already exists: {} (use --vault-passwd to change the master password)", path.display());
+        return;
+    }
+    let Some(master) = read_new_master() else { return };
+    match Vault::create(&path, &master, KdfParams::default()) {
+        Ok(_) => println!("Vault created: {}", path.display()),
+        Err(e) => println!("Error: {}", e),
+    }
+}
+
+fn vault_passwd() {
+    let result = Vault::open_default().and_then(|mut vault| {
+        let current = read_secret("Current vault master password: ");
+        vault.unlock(&current)?;
+        let Some(new) = read_new_master() else { return Ok(false) };
+        vault.change_master_password(&current, &new)?;
+        Ok(true)
+    });
+    match result {
+        Ok(true) => println!("Vault master password changed."),
+        Ok(false) => {}
+        Err(e) => println!("Error: {}", e),
+    }
+}
+
+fn vault_list() {
+    match unlock_vault().and_then(|mut vault| vault.ids()) {
+        Ok(ids) if ids.is_empty() => println!("Vault is empty."),
+        Ok(ids) => ids.iter().for_each(|id| println!("  {}", id)),
+        Err(e) => println!("Error: {}", e),
+    }
+}
+
+/// 프로필의 패스워드 / passphrase를 입력받아 볼트에 저장
+fn vault_save(name: &str) {
+    let profile = match ProfileStore::open_default() {
+        Ok(store) => store.get(name).cloned(),
+        Err(e) => {
+            println!("Error: {}", e);
+            return;
+        }
+    };
+    let Some(profile) = profile else {
+        println!("Error: no such profile: {} (see --list-profiles)", name);
+        return;
+    };
+    let targets: Vec<_> = profile.auth.iter()
+        .filter_map(|auth| profile.secret_id(auth).map(|id| (auth, id)))
+        .collect();
+    if targets.is_empty() {
+        println!("Profile @{} has no password or key file to store.", profile.name);
+        return;
+    }
+
+    let result = unlock_vault().and_then(|mut vault| {
+        let user = profile.to_connect_config().map(|c| c.username).unwrap_or_default();
+        for (auth, id) in targets {
+            let secret = match auth {
+                ProfileAuth::PublicKey(path) => read_passphrase(path),
+                _ => read_password(&user, &profile.host),
+            };
+            // 입력을 기다리는 동안 유휴 시간이 지났으면 키를 바로 소거
+            if vault.expire_if_idle() {
+                return Err(Error::VaultLocked);
+            }
+            if secret.is_empty() {
+                println!("Skipped {}", id);
+                continue;
+            }
+            vault.set(&id, secret)?;
+            println!("Stored {}", id);
+        }
+        Ok(())
+    });
+    if let Err(e) = result {
+        println!("Error: {}", e);
+    }
+}
+
+fn vault_remove(id: &str) {
+    match unlock_vault().and_then(|mut vault| vault.remove(id)) {
+        Ok(true) => println!("Removed {}", id),
+        Ok(false) => println!("No such secret: {}", id),
+        Err(e) => println!("Error: {}", e),
+    }
+}
+
+/// 암호화된 키면 passphrase 입력받음 (키 로딩 자체는 connect에서 다시 수행)
+/// 명령줄 -i 키(cli_identity)를 못 읽으면 에러, 설정 파일 키는 connect에서 실패로 기록됨
+fn prepare_auth(methods: &mut [AuthMethod], cli_identity: Option<&Path>) -> Result<(), Error> {
+    for method in methods.iter_mut() {
+        let AuthMethod::PublicKey { private_key_path, passphrase } = method else { continue };
+        // 볼트에서 이미 채워진 passphrase
+        if passphrase.is_some() {
+            continue;
+        }
+        match load_private_key(private_key_path, None) {
+            Ok(_) => {}
+            Err(Error::KeyEncrypted(_)) => *passphrase = Some(read_passphrase(private_key_path)),
+            Err(e) if cli_identity == Some(private_key_path.as_path()) => return Err(e),
+            Err(_) => {}
+        }
+    }
+    Ok(())
+}
+
+fn read_password(username: &str, host: &str) -> Secret {
+    read_secret(&format!("{}@{}'s password: ", username, host))
+}
+
+fn read_proxy_password(username: &str, host: &str) -> Secret {
+    read_secret(&format!("Proxy password for {}@{}: ", username, host))
+}
+
+fn read_passphrase(path: &Path) -> Secret {
+    read_secret(&format!("Enter passphrase for key '{}': ", path.display()))
 }
 
-fn read_password(username: &str, host: &str) -> String {
-    eprint!("{}@{}'s password: ", username, host);
-    let mut password = String::new();
-    std::io::stdin().read_line(&mut password).unwrap();
-    password.trim().to_string()
+/// 에코 없이 한 줄 입력 → Secret (입력 버퍼도 drop 시 소거, EOF면 빈 값)
+fn read_secret(prompt: &str) -> Secret {
+    eprint!("{}", prompt);
+    tty::read_line(false)
+        .map(|line| Secret::from(line.trim()))
+        .unwrap_or_default()
 }
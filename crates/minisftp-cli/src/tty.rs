@@ -0,0 +1,72 @@
+// miniSFTP CLI 터미널 입력
+// author: kodeholic (powered by Claude)
+//
+// 패스워드 / passphrase / OTP 입력 시 화면 에코 끄기
+//   unix : termios ECHO 해제 (ECHONL은 유지 → 엔터 후 줄바꿈은 표시), 끝나면 원래 설정 복원
+//   그 외 : 에코를 끌 수 없어 그대로 입력
+// stdin이 터미널이 아니면(파이프 입력 등) 설정을 건드리지 않음
+
+use std::io;
+
+use minisftp_core::secret::Zeroizing;
+
+/// 한 줄 입력 (줄바꿈 제거, 입력 버퍼는 drop 시 소거), EOF면 None
+///
+/// echo=false: 입력 내용을 화면에 표시하지 않음
+pub fn read_line(echo: bool) -> Option<Zeroizing<String>> {
+    let _guard = if echo { None } else { EchoOff::new() };
+    let mut line = Zeroizing::new(String::new());
+    if io::stdin().read_line(&mut line).unwrap_or(0) == 0 {
+        return None;
+    }
+    let len = line.trim_end_matches(['\r', '\n']).len();
+    line.truncate(len);
+    Some(line)
+}
+
+/// 에코 해제 상태 (drop 시 원래 termios 복원)
+#[cfg(unix)]
+struct EchoOff(libc::termios);
+
+#[cfg(unix)]
+impl EchoOff {
+    fn new() -> Option<Self> {
+        // SAFETY: fd 0에 대한 tcgetattr / tcsetattr, termios는 tcgetattr가 채움
+        unsafe {
+            if libc::isatty(libc::STDIN_FILENO) != 1 {
+                return None;
+            }
+            let mut original: libc::termios = std::mem::zeroed();
+            if libc::tcgetattr(libc::STDIN_FILENO, &mut original) != 0 {
+                return None;
+            }
+            let mut hidden = original;
+            hidden.c_lflag &= !libc::ECHO;
+            hidden.c_lflag |= libc::ECHONL;
+            if libc::tcsetattr(libc::STDIN_FILENO, libc::TCSANOW, &hidden) != 0 {
+                return None;
+            }
+            Some(Self(original))
+        }
+    }
+}
+
+#[cfg(unix)]
+impl Drop for EchoOff {
+    fn drop(&mut self) {
+        // SAFETY: new()에서 읽은 원래 설정으로 복원
+        unsafe {
+            libc::tcsetattr(libc::STDIN_FILENO, libc::TCSANOW, &self.0);
+        }
+    }
+}
+
+#[cfg(not(unix))]
+struct EchoOff;
+
+#[cfg(not(unix))]
+impl EchoOff {
+    fn new() -> Option<Self> {
+        None
+    }
+}
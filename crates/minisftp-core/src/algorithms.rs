@@ -0,0 +1,309 @@
+// miniSFTP SSH Algorithm Preferences
+// author: kodeholic (powered by Claude)
+//
+// ConnectConfig::algorithms(이름 목록) → russh Preferred 변환 + 협상 결과 계산
+//
+// - 빈 목록 → russh 기본값
+// - 목록 문자열은 OpenSSH 표기 (Ciphers, MACs, KexAlgorithms, HostKeyAlgorithms)
+//   "a,b"  : 목록 그대로
+//   "+a,b" : 기본값 뒤에 추가      (레거시 장비: +diffie-hellman-group14-sha1)
+//   "-a*"  : 기본값에서 제거 (와일드카드 허용)
+//   "^a,b" : 기본값 앞에 추가
+// - russh는 협상 결과를 노출하지 않음
+//   → 서버 KEXINIT(평문)을 전송 스트림에서 읽어 클라이언트 규칙대로 다시 계산
+//     (클라이언트 목록 순서대로, 서버도 지원하는 첫 번째 알고리즘)
+
+use std::borrow::Cow;
+use std::fmt;
+
+use russh::keys::key::ALL_KEY_TYPES;
+use russh::keys::Algorithm;
+use russh::{cipher, compression, kex, mac, Preferred};
+
+use crate::config::Algorithms;
+use crate::error::{Error, Result};
+use crate::utils::wildcard_match;
+
+/// 알고리즘 종류
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AlgorithmKind {
+    Kex,
+    HostKey,
+    Cipher,
+    Mac,
+    Compression,
+}
+
+impl fmt::Display for AlgorithmKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            AlgorithmKind::Kex         => write!(f, "key exchange"),
+            AlgorithmKind::HostKey     => write!(f, "host key"),
+            AlgorithmKind::Cipher      => write!(f, "cipher"),
+            AlgorithmKind::Mac         => write!(f, "MAC"),
+            AlgorithmKind::Compression => write!(f, "compression"),
+        }
+    }
+}
+
+/// 협상된 알고리즘 (cipher / mac / compression은 클라이언트 → 서버 방향)
+#[derive(Debug, Clone)]
+pub struct NegotiatedAlgorithms {
+    pub kex: String,
+    pub host_key: String,
+    pub cipher: String,
+    /// AEAD cipher(chacha20-poly1305, aes-gcm)면 "<implicit>"
+    pub mac: String,
+    pub compression: String,
+}
+
+impl fmt::Display for NegotiatedAlgorithms {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "kex={} hostkey={} cipher={} mac={} compression={}",
+            self.kex, self.host_key, self.cipher, self.mac, self.compression)
+    }
+}
+
+/// 서버 KEXINIT의 알고리즘 목록
+#[derive(Debug, Clone, Default)]
+pub(crate) struct ServerAlgorithms {
+    pub kex: Vec<String>,
+    pub host_key: Vec<String>,
+    pub cipher: Vec<String>,
+    pub mac: Vec<String>,
+    pub compression: Vec<String>,
+}
+
+/// russh가 지원하는 알고리즘 이름
+pub fn supported(kind: AlgorithmKind) -> Vec<&'static str> {
+    match kind {
+        AlgorithmKind::Kex => kex::ALL_KEX_ALGORITHMS.iter().map(|n| n.as_ref()).filter(|n| *n != "none").collect(),
+        AlgorithmKind::HostKey => ALL_KEY_TYPES.iter().map(Algorithm::as_str).collect(),
+        AlgorithmKind::Cipher => cipher::ALL_CIPHERS.iter().map(|n| n.as_ref())
+            .filter(|n| !matches!(*n, "none" | "clear"))
+            .collect(),
+        AlgorithmKind::Mac => mac::ALL_MAC_ALGORITHMS.iter().map(|n| n.as_ref()).filter(|n| *n != "none").collect(),
+        AlgorithmKind::Compression => compression::ALL_COMPRESSION_ALGORITHMS.iter().map(|n| n.as_ref()).collect(),
+    }
+}
+
+/// russh 기본 우선순위 (kex의 ext-info / strict-kex 표시는 제외)
+pub fn defaults(kind: AlgorithmKind) -> Vec<String> {
+    let pref = Preferred::DEFAULT;
+    let names: Vec<&str> = match kind {
+        AlgorithmKind::Kex => pref.kex.iter().map(|n| n.as_ref()).filter(|n| !is_kex_marker(n)).collect(),
+        AlgorithmKind::HostKey => pref.key.iter().map(Algorithm::as_str).collect(),
+        AlgorithmKind::Cipher => pref.cipher.iter().map(|n| n.as_ref()).collect(),
+        AlgorithmKind::Mac => pref.mac.iter().map(|n| n.as_ref()).collect(),
+        AlgorithmKind::Compression => pref.compression.iter().map(|n| n.as_ref()).collect(),
+    };
+    names.into_iter().map(str::to_string).collect()
+}
+
+/// OpenSSH 표기 목록 해석 ("a,b" / "+a" / "-a*" / "^a") → 이름 목록
+///
+/// 이름 검증은 하지 않음 (connect 시 지원하지 않는 이름이면 Error::Algorithm)
+pub fn parse_spec(kind: AlgorithmKind, spec: &str) -> Vec<String> {
+    let names = |s: &str| -> Vec<String> {
+        s.split(',').map(str::trim).filter(|n| !n.is_empty()).map(str::to_string).collect()
+    };
+    let defaults = defaults(kind);
+
+    if let Some(rest) = spec.strip_prefix('+') {
+        let mut list = defaults;
+        for name in names(rest) {
+            if !list.contains(&name) {
+                list.push(name);
+            }
+        }
+        list
+    } else if let Some(rest) = spec.strip_prefix('-') {
+        let patterns = names(rest);
+        defaults.into_iter().filter(|n| !patterns.iter().any(|p| wildcard_match(p, n))).collect()
+    } else if let Some(rest) = spec.strip_prefix('^') {
+        let mut list = names(rest);
+        for name in defaults {
+            if !list.contains(&name) {
+                list.push(name);
+            }
+        }
+        list
+    } else {
+        names(spec)
+    }
+}
+
+/// Algorithms → russh Preferred (빈 목록은 기본값, 지원하지 않는 이름이면 Error::Algorithm)
+pub(crate) fn preferred(algorithms: &Algorithms) -> Result<Preferred> {
+    let mut pref = Preferred::DEFAULT;
+
+    if !algorithms.kex.is_empty() {
+        let mut list = convert(AlgorithmKind::Kex, &algorithms.kex, |s| kex::Name::try_from(s).ok())?;
+        // ext-info / strict kex(Terrapin 대응) 표시는 사용자 목록과 무관하게 유지
+        list.extend([kex::EXTENSION_SUPPORT_AS_CLIENT, kex::EXTENSION_OPENSSH_STRICT_KEX_AS_CLIENT]);
+        pref.kex = Cow::Owned(list);
+    }
+    if !algorithms.host_key.is_empty() {
+        pref.key = Cow::Owned(convert(AlgorithmKind::HostKey, &algorithms.host_key, |s| Algorithm::new(s).ok())?);
+    }
+    if !algorithms.cipher.is_empty() {
+        pref.cipher = Cow::Owned(convert(AlgorithmKind::Cipher, &algorithms.cipher, |s| cipher::Name::try_from(s).ok())?);
+    }
+    if !algorithms.mac.is_empty() {
+        pref.mac = Cow::Owned(convert(AlgorithmKind::Mac, &algorithms.mac, |s| mac::Name::try_from(s).ok())?);
+    }
+    if !algorithms.compression.is_empty() {
+        pref.compression = Cow::Owned(convert(
+            AlgorithmKind::Compression, &algorithms.compression, |s| compression::Name::try_from(s).ok(),
+        )?);
+    }
+    Ok(pref)
+}
+
+/// 서버 목록과 클라이언트 우선순위로 협상 결과 계산 (russh 클라이언트와 같은 규칙)
+pub(crate) fn negotiate(pref: &Preferred, server: &ServerAlgorithms) -> Option<NegotiatedAlgorithms> {
+    let kex = select(pref.kex.iter().map(|n| n.as_ref()), &server.kex)?;
+    let host_key = select(pref.key.iter().map(Algorithm::as_str), &server.host_key)?;
+    let cipher = select(pref.cipher.iter().map(|n| n.as_ref()), &server.cipher)?;
+    let mac = if is_aead(&cipher) {
+        "<implicit>".to_string()
+    } else {
+        select(pref.mac.iter().map(|n| n.as_ref()), &server.mac)?
+    };
+    let compression = select(pref.compression.iter().map(|n| n.as_ref()), &server.compression)?;
+    Some(NegotiatedAlgorithms { kex, host_key, cipher, mac, compression })
+}
+
+/// 공통 알고리즘이 없는 첫 종류와 서버 지원 목록 (협상 실패 메시지용)
+pub(crate) fn mismatch(pref: &Preferred, server: &ServerAlgorithms) -> Option<String> {
+    let checks: [(AlgorithmKind, Vec<&str>, &Vec<String>); 5] = [
+        (AlgorithmKind::Kex, pref.kex.iter().map(|n| n.as_ref()).collect(), &server.kex),
+        (AlgorithmKind::HostKey, pref.key.iter().map(Algorithm::as_str).collect(), &server.host_key),
+        (AlgorithmKind::Cipher, pref.cipher.iter().map(|n| n.as_ref()).collect(), &server.cipher),
+        (AlgorithmKind::Mac, pref.mac.iter().map(|n| n.as_ref()).collect(), &server.mac),
+        (AlgorithmKind::Compression, pref.compression.iter().map(|n| n.as_ref()).collect(), &server.compression),
+    ];
+    let (kind, _, offered) = checks.into_iter().find(|(_, client, server)| select(client.iter().copied(), server).is_none())?;
+    let offered: Vec<&str> = offered.iter().map(String::as_str).filter(|n| !is_kex_marker(n)).collect();
+    Some(format!("no common {} algorithm (server offers: {})", kind, offered.join(",")))
+}
+
+/// 이름 목록 → russh Name 목록
+fn convert<T>(kind: AlgorithmKind, names: &[String], parse: impl Fn(&str) -> Option<T>) -> Result<Vec<T>> {
+    names.iter()
+        .map(|name| parse(name).ok_or_else(|| Error::Algorithm(format!("unsupported {} algorithm: {}", kind, name))))
+        .collect()
+}
+
+/// 클라이언트 목록 순서대로, 서버도 지원하는 첫 번째
+fn select<'a>(client: impl Iterator<Item = &'a str>, server: &[String]) -> Option<String> {
+    client.into_iter().find(|c| server.iter().any(|s| s == c)).map(str::to_string)
+}
+
+fn is_kex_marker(name: &str) -> bool {
+    name.starts_with("ext-info-") || name.starts_with("kex-strict-")
+}
+
+fn is_aead(cipher: &str) -> bool {
+    cipher == cipher::CHACHA20_POLY1305.as_ref() || cipher.ends_with("-gcm@openssh.com")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn list(names: &[&str]) -> Vec<String> {
+        names.iter().map(|n| n.to_string()).collect()
+    }
+
+    #[test]
+    fn spec_plain_list() {
+        assert_eq!(
+            parse_spec(AlgorithmKind::Cipher, "aes128-ctr, aes256-gcm@openssh.com,,"),
+            list(&["aes128-ctr", "aes256-gcm@openssh.com"]),
+        );
+        // 이름 검증은 preferred()에서
+        assert_eq!(parse_spec(AlgorithmKind::Mac, "no-such-mac"), list(&["no-such-mac"]));
+    }
+
+    #[test]
+    fn spec_append() {
+        let defaults = defaults(AlgorithmKind::Kex);
+        let spec = parse_spec(AlgorithmKind::Kex, "+diffie-hellman-group14-sha1");
+        assert_eq!(spec[..defaults.len()], defaults[..]);
+        assert_eq!(spec.last().map(String::as_str), Some("diffie-hellman-group14-sha1"));
+        // 이미 있는 이름은 그 자리 그대로
+        assert_eq!(parse_spec(AlgorithmKind::Kex, &format!("+{}", defaults[0])), defaults);
+    }
+
+    #[test]
+    fn spec_remove_wildcard() {
+        let defaults = defaults(AlgorithmKind::Cipher);
+        let spec = parse_spec(AlgorithmKind::Cipher, "-aes*-ctr,chacha20*");
+        assert!(!spec.is_empty());
+        assert!(spec.iter().all(|n| !n.ends_with("-ctr") && !n.starts_with("chacha20")));
+        // 남은 이름은 기본 순서 유지
+        let expected: Vec<String> = defaults.into_iter()
+            .filter(|n| !n.ends_with("-ctr") && !n.starts_with("chacha20"))
+            .collect();
+        assert_eq!(spec, expected);
+    }
+
+    #[test]
+    fn spec_prepend() {
+        let defaults = defaults(AlgorithmKind::Cipher);
+        let spec = parse_spec(AlgorithmKind::Cipher, "^aes128-ctr");
+        assert_eq!(spec[0], "aes128-ctr");
+        assert_eq!(spec.len(), defaults.len());
+        assert_eq!(spec.iter().filter(|n| *n == "aes128-ctr").count(), 1);
+    }
+
+    fn server(cipher: &[&str], mac: &[&str]) -> ServerAlgorithms {
+        ServerAlgorithms {
+            kex: list(&["diffie-hellman-group14-sha256", "curve25519-sha256", "kex-strict-s-v00@openssh.com"]),
+            host_key: list(&["rsa-sha2-512", "ssh-ed25519"]),
+            cipher: list(cipher),
+            mac: list(mac),
+            compression: list(&["none"]),
+        }
+    }
+
+    #[test]
+    fn negotiate_follows_client_order() {
+        let algorithms = Algorithms {
+            cipher: list(&["aes128-ctr", "aes256-ctr"]),
+            mac: list(&["hmac-sha2-256", "hmac-sha2-512"]),
+            ..Algorithms::default()
+        };
+        let pref = preferred(&algorithms).unwrap();
+        // 서버 순서와 무관하게 클라이언트 목록에서 서버도 지원하는 첫 번째
+        let result = negotiate(&pref, &server(&["aes256-ctr", "aes128-ctr"], &["hmac-sha2-512", "hmac-sha2-256"])).unwrap();
+        assert_eq!(result.kex, "curve25519-sha256");
+        assert_eq!(result.host_key, "ssh-ed25519");
+        assert_eq!(result.cipher, "aes128-ctr");
+        assert_eq!(result.mac, "hmac-sha2-256");
+        assert_eq!(result.compression, "none");
+
+        let result = negotiate(&pref, &server(&["aes256-ctr"], &["hmac-sha2-512"])).unwrap();
+        assert_eq!((result.cipher.as_str(), result.mac.as_str()), ("aes256-ctr", "hmac-sha2-512"));
+    }
+
+    #[test]
+    fn negotiate_aead_and_mismatch() {
+        let algorithms = Algorithms { cipher: list(&["chacha20-poly1305@openssh.com"]), ..Algorithms::default() };
+        let pref = preferred(&algorithms).unwrap();
+        // AEAD면 MAC 목록이 겹치지 않아도 협상
+        let result = negotiate(&pref, &server(&["chacha20-poly1305@openssh.com"], &["no-common-mac"])).unwrap();
+        assert_eq!(result.mac, "<implicit>");
+
+        let server = server(&["aes128-cbc"], &["hmac-sha2-256"]);
+        assert!(negotiate(&pref, &server).is_none());
+        assert_eq!(mismatch(&pref, &server).as_deref(), Some("no common cipher algorithm (server offers: aes128-cbc)"));
+    }
+
+    #[test]
+    fn preferred_rejects_unknown_name() {
+        let algorithms = Algorithms { mac: list(&["hmac-md5-nope"]), ..Algorithms::default() };
+        assert!(matches!(preferred(&algorithms), Err(Error::Algorithm(_))));
+    }
+}
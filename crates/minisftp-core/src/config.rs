@@ -1,22 +1,305 @@
 // miniSFTP Connection Config
 // author: kodeholic (powered by Claude)
 //
-// Tauri 연계 시 serde derive 추가하면 JSON 직렬화 가능
+// 저장 대상(프로필)에 쓰이는 타입은 serde derive (Tauri ↔ JSON)
 
 use std::path::PathBuf;
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+
+use crate::secret::Secret;
 
 #[derive(Debug, Clone)]
 pub struct ConnectConfig {
     pub host: String,
     pub port: u16,
     pub username: String,
-    pub auth: AuthMethod,
+    /// 인증 방식 (순서대로 시도, 예: agent → 키 파일 → 패스워드)
+    /// publickey,password처럼 2단계 인증을 요구하는 서버도 목록 순서대로 충족
+    pub auth_methods: Vec<AuthMethod>,
+    /// 호스트키 검증 정책
+    pub host_key_policy: HostKeyPolicy,
+    /// known_hosts 경로 (None → ~/.ssh/known_hosts)
+    pub known_hosts_path: Option<PathBuf>,
+    /// 점프 호스트 (ProxyJump, 순서대로 경유 → 마지막 hop에서 host:port로 direct-tcpip)
+    /// 호스트키 검증은 host_key_policy / known_hosts_path를 공유
+    pub jump_hosts: Vec<JumpHost>,
+    /// 프록시 (None → TCP 직접 연결), 점프 호스트가 있으면 첫 hop 연결에 적용
+    pub proxy: Option<ProxyConfig>,
+    /// 연결이 끊겼을 때 SftpSession::reconnect()의 재시도 정책
+    pub reconnect: ReconnectPolicy,
+    /// 연결 단계별 타임아웃
+    pub timeouts: Timeouts,
+    /// SSH keepalive / 유휴 타임아웃
+    pub keepalive: Keepalive,
+    /// 알고리즘 우선순위 (kex, host key, cipher, MAC, compression)
+    pub algorithms: Algorithms,
+}
+
+/// SSH 알고리즘 우선순위 (빈 목록 → russh 기본값)
+///
+/// 이름은 OpenSSH 표기 그대로, 앞쪽이 우선 (예: "diffie-hellman-group14-sha1", "aes128-cbc", "zlib@openssh.com")
+/// OpenSSH의 "+a" / "-a*" / "^a" 표기는 algorithms::parse_spec()으로 변환
+/// 점프 호스트에도 같은 목록 적용
+#[derive(Debug, Clone, Default)]
+pub struct Algorithms {
+    pub kex: Vec<String>,
+    pub host_key: Vec<String>,
+    pub cipher: Vec<String>,
+    pub mac: Vec<String>,
+    /// "zlib@openssh.com"(인증 후 압축), "zlib", "none"
+    pub compression: Vec<String>,
+}
+
+/// 연결 단계별 타임아웃 (None → 제한 없음)
+///
+/// 만료 시 Error::Timeout { state } — 만료된 ConnectionState 표시
+/// 점프 호스트는 hop마다 같은 값 적용
+///
+/// 호스트키 확인 / keyboard-interactive prompter는 동기 호출이라 입력을 기다리는 동안은
+/// 만료를 보장하지 않음 → 사용자 응답 대기 시간은 prompter 쪽에서 제한
+#[derive(Debug, Clone)]
+pub struct Timeouts {
+    /// TCP 연결 (프록시 협상, 점프 호스트의 direct-tcpip 채널 포함)
+    pub tcp_connect: Option<Duration>,
+    /// SSH 버전 교환 + 키 교환
+    pub handshake: Option<Duration>,
+    /// 인증
+    pub auth: Option<Duration>,
+    /// 세션 채널 + sftp 서브시스템 + SFTP 초기화
+    pub sftp_init: Option<Duration>,
+}
+
+impl Default for Timeouts {
+    fn default() -> Self {
+        Self {
+            tcp_connect: Some(Duration::from_secs(15)),
+            handshake: Some(Duration::from_secs(60)),
+            auth: None,
+            sftp_init: Some(Duration::from_secs(30)),
+        }
+    }
+}
+
+/// SSH keepalive 설정 (OpenSSH ServerAliveInterval / ServerAliveCountMax)
+///
+/// NAT / 방화벽이 유휴 연결을 끊지 않도록 주기적으로 keepalive@openssh.com 전송
+/// max_missed번 연속 응답이 없으면 연결 종료 → 끊김 감지 / 재연결
+#[derive(Debug, Clone)]
+pub struct Keepalive {
+    /// 전송 주기 (None → 보내지 않음)
+    pub interval: Option<Duration>,
+    /// 응답 없이 허용할 keepalive 수
+    pub max_missed: usize,
+}
+
+impl Default for Keepalive {
+    fn default() -> Self {
+        Self {
+            interval: Some(Duration::from_secs(30)),
+            max_missed: 3,
+        }
+    }
+}
+
+/// 재연결 정책 (지수 백오프)
+///
+/// 첫 시도는 즉시, 이후 initial_delay × multiplier^(n-1) 대기 (max_delay 상한)
+/// 인증 실패 / 호스트키 거부처럼 재시도해도 소용없는 에러는 즉시 중단
+#[derive(Debug, Clone)]
+pub struct ReconnectPolicy {
+    /// 최대 시도 횟수 (0 → 재연결 안 함)
+    pub max_attempts: u32,
+    pub initial_delay: Duration,
+    pub max_delay: Duration,
+    pub multiplier: f64,
+}
+
+impl Default for ReconnectPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 5,
+            initial_delay: Duration::from_secs(1),
+            max_delay: Duration::from_secs(30),
+            multiplier: 2.0,
+        }
+    }
+}
+
+impl ReconnectPolicy {
+    /// 재연결 안 함
+    pub fn disabled() -> Self {
+        Self { max_attempts: 0, ..Self::default() }
+    }
+
+    /// attempt번째 시도가 실패한 뒤 다음 시도까지 대기 시간 (attempt: 1부터)
+    pub fn delay(&self, attempt: u32) -> Duration {
+        let factor = self.multiplier.max(1.0).powi(attempt.saturating_sub(1) as i32);
+        self.initial_delay.mul_f64(factor).min(self.max_delay)
+    }
+}
+
+/// 프록시 전송 방식
+#[derive(Debug, Clone)]
+pub enum ProxyConfig {
+    /// 명령 실행 후 stdin/stdout으로 SSH 통신 (OpenSSH ProxyCommand, %h %p %r 치환)
+    Command(String),
+    /// SOCKS5 (대상 호스트 이름은 프록시가 해석)
+    Socks5 {
+        host: String,
+        port: u16,
+        credentials: Option<ProxyCredentials>,
+    },
+    /// HTTP CONNECT 터널
+    HttpConnect {
+        host: String,
+        port: u16,
+        credentials: Option<ProxyCredentials>,
+    },
+}
+
+/// 프록시 인증 정보 (SOCKS5 username/password, HTTP Basic)
+#[derive(Debug, Clone)]
+pub struct ProxyCredentials {
+    pub username: String,
+    pub password: Secret,
+}
+
+impl ProxyConfig {
+    /// URL 파싱: socks5://[user[:pass]@]host[:port], http://[user[:pass]@]host[:port]
+    ///
+    /// 포트 생략 시 SOCKS5 1080, HTTP 8080. password가 없으면 빈 문자열
+    pub fn from_url(url: &str) -> Option<ProxyConfig> {
+        let (scheme, rest) = url.split_once("://")?;
+        let rest = rest.trim_end_matches('/');
+        let (userinfo, host_port) = match rest.rsplit_once('@') {
+            Some((u, h)) => (Some(u), h),
+            None => (None, rest),
+        };
+        let credentials = userinfo.map(|u| {
+            let (username, password) = u.split_once(':').unwrap_or((u, ""));
+            ProxyCredentials { username: username.to_string(), password: Secret::from(password) }
+        });
+
+        let socks = match scheme.to_lowercase().as_str() {
+            "socks5" | "socks5h" => true,
+            "http"               => false,
+            _ => return None,
+        };
+        let (host, port) = match host_port.strip_prefix('[') {
+            Some(v6) => {
+                let (host, tail) = v6.split_once(']')?;
+                (host, tail.strip_prefix(':').map(str::parse).transpose().ok()?)
+            }
+            None => match host_port.rsplit_once(':') {
+                Some((h, p)) => (h, Some(p.parse().ok()?)),
+                None => (host_port, None),
+            },
+        };
+        if host.is_empty() {
+            return None;
+        }
+
+        let host = host.to_string();
+        Some(if socks {
+            ProxyConfig::Socks5 { host, port: port.unwrap_or(1080), credentials }
+        } else {
+            ProxyConfig::HttpConnect { host, port: port.unwrap_or(8080), credentials }
+        })
+    }
+}
+
+/// 점프 호스트(bastion) 한 단계
+#[derive(Debug, Clone)]
+pub struct JumpHost {
+    pub host: String,
+    pub port: u16,
+    pub username: String,
+    /// 이 hop의 인증 방식 (대상 서버와 별개)
+    pub auth_methods: Vec<AuthMethod>,
 }
 
+impl JumpHost {
+    pub fn addr(&self) -> String {
+        format!("{}:{}", self.host, self.port)
+    }
+}
+
+/// 호스트키 검증 정책 (OpenSSH StrictHostKeyChecking 대응)
+///
+/// 키 변경(Changed) / 폐기(@revoked)는 AcceptAny를 제외한 모든 정책에서 거부
+/// 직렬화 값은 OpenSSH 표기 ("yes", "accept-new", "ask", "no")
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub enum HostKeyPolicy {
+    /// known_hosts에 있는 키만 허용 (yes)
+    #[serde(rename = "yes")]
+    Strict,
+    /// 처음 보는 호스트는 신뢰하고 known_hosts에 추가 (accept-new, TOFU)
+    #[serde(rename = "accept-new")]
+    AcceptNew,
+    /// 처음 보는 호스트는 HostKeyPrompter로 확인, 수락 시 추가 (ask)
+    #[default]
+    #[serde(rename = "ask")]
+    Ask,
+    /// 검증 생략 (no) — 실험/랩 환경 전용, MITM에 취약
+    #[serde(rename = "no")]
+    AcceptAny,
+}
+
+impl HostKeyPolicy {
+    /// OpenSSH StrictHostKeyChecking 값 해석 (yes / accept-new / ask / no, off)
+    pub fn from_openssh(value: &str) -> Option<Self> {
+        match value.to_lowercase().as_str() {
+            "yes"        => Some(HostKeyPolicy::Strict),
+            "accept-new" => Some(HostKeyPolicy::AcceptNew),
+            "ask"        => Some(HostKeyPolicy::Ask),
+            "no" | "off" => Some(HostKeyPolicy::AcceptAny),
+            _ => None,
+        }
+    }
+
+    /// OpenSSH 표기
+    pub fn as_openssh(&self) -> &'static str {
+        match self {
+            HostKeyPolicy::Strict    => "yes",
+            HostKeyPolicy::AcceptNew => "accept-new",
+            HostKeyPolicy::Ask       => "ask",
+            HostKeyPolicy::AcceptAny => "no",
+        }
+    }
+}
+
+/// 인증 방식 (패스워드 / passphrase는 Secret → Debug 출력 시 가려짐)
 #[derive(Debug, Clone)]
 pub enum AuthMethod {
-    Password(String),
-    PublicKey { private_key_path: PathBuf },
+    Password(Secret),
+    /// 개인키 파일 인증 (OpenSSH / PEM, ed25519 · ECDSA · RSA)
+    /// 암호화된 키는 passphrase 필요 (None이면 Error::KeyEncrypted)
+    PublicKey {
+        private_key_path: PathBuf,
+        passphrase: Option<Secret>,
+    },
+    /// ssh-agent 인증 (SSH_AUTH_SOCK)
+    /// identity: None이면 agent의 키를 순서대로 시도,
+    ///           Some이면 fingerprint("SHA256:...") 또는 comment가 일치하는 키만 사용
+    Agent { identity: Option<String> },
+    /// keyboard-interactive 인증 (PAM, OTP 등)
+    /// 질문 응답은 SftpSession::set_prompter()로 등록한 prompter가 담당
+    /// submethods: 서버에 전달할 힌트 (보통 None)
+    KeyboardInteractive { submethods: Option<String> },
+}
+
+impl AuthMethod {
+    /// 로그/에러 표시용 이름
+    pub fn name(&self) -> &'static str {
+        match self {
+            AuthMethod::Password(_)                 => "password",
+            AuthMethod::PublicKey { .. }            => "publickey",
+            AuthMethod::Agent { .. }                => "agent",
+            AuthMethod::KeyboardInteractive { .. }  => "keyboard-interactive",
+        }
+    }
 }
 
 impl ConnectConfig {
@@ -24,3 +307,57 @@ impl ConnectConfig {
         format!("{}:{}", self.host, self.port)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// (socks 여부, host, port, (user, password))
+    fn proxy(url: &str) -> (bool, String, u16, Option<(String, String)>) {
+        let creds = |c: Option<ProxyCredentials>| c.map(|c| (c.username, c.password.expose().to_string()));
+        match ProxyConfig::from_url(url) {
+            Some(ProxyConfig::Socks5 { host, port, credentials })      => (true, host, port, creds(credentials)),
+            Some(ProxyConfig::HttpConnect { host, port, credentials }) => (false, host, port, creds(credentials)),
+            other => panic!("{}: {:?}", url, other),
+        }
+    }
+
+    #[test]
+    fn proxy_url_scheme_and_default_port() {
+        assert_eq!(proxy("socks5://127.0.0.1"), (true, "127.0.0.1".to_string(), 1080, None));
+        assert_eq!(proxy("socks5h://proxy:9050/"), (true, "proxy".to_string(), 9050, None));
+        assert_eq!(proxy("SOCKS5://proxy"), (true, "proxy".to_string(), 1080, None));
+        assert_eq!(proxy("http://proxy"), (false, "proxy".to_string(), 8080, None));
+        assert_eq!(proxy("http://proxy:3128"), (false, "proxy".to_string(), 3128, None));
+        assert_eq!(proxy("socks5://[::1]:1081"), (true, "::1".to_string(), 1081, None));
+        assert_eq!(proxy("http://[::1]"), (false, "::1".to_string(), 8080, None));
+    }
+
+    #[test]
+    fn proxy_url_credentials() {
+        let creds = |u: &str, p: &str| Some((u.to_string(), p.to_string()));
+        assert_eq!(proxy("socks5://user:pw@proxy").3, creds("user", "pw"));
+        // 패스워드가 없으면 빈 문자열 (CLI가 입력받음)
+        assert_eq!(proxy("http://user@proxy:3128").3, creds("user", ""));
+        // 마지막 @ 기준으로 host 분리, 첫 : 기준으로 패스워드 분리
+        assert_eq!(proxy("http://user:p@ss:w@proxy"), (false, "proxy".to_string(), 8080, creds("user", "p@ss:w")));
+    }
+
+    #[test]
+    fn proxy_url_invalid() {
+        for url in [
+            "proxy:1080",
+            "ftp://proxy",
+            "socks4://proxy",
+            "socks5://",
+            "socks5://user@",
+            "socks5://proxy:port",
+            "socks5://proxy:70000",
+            "socks5://[::1",
+            "socks5://[::1]:x",
+            "http://:8080",
+        ] {
+            assert!(ProxyConfig::from_url(url).is_none(), "{}", url);
+        }
+    }
+}
@@ -6,18 +6,79 @@
 // From<io::Error>: ? 연산자로 IO 에러 자동 변환
 
 use std::fmt;
+use std::path::PathBuf;
+use russh_sftp::client::error::Error as RusshSftpError;
+use russh_sftp::protocol::StatusCode;
+use crate::auth::AuthAttempt;
 use crate::state::ConnectionState;
 
 #[derive(Debug)]
 pub enum Error {
     Io(std::io::Error),
     InvalidTransition {
-        from: ConnectionState,
-        to: ConnectionState,
+        from: Box<ConnectionState>,
+        to: Box<ConnectionState>,
     },
     Protocol(String),
-    Auth(String),
-    Sftp(String),
+    /// 인증 실패 (attempts: 시도한 메서드별 결과, 순서대로)
+    Auth {
+        message: String,
+        attempts: Vec<AuthAttempt>,
+    },
+    /// SFTP 요청 실패 (kind: 상태 코드 분류, path: 대상 리모트 경로, source: russh-sftp 원인)
+    Sftp {
+        kind: SftpErrorKind,
+        path: Option<String>,
+        message: String,
+        source: Option<Box<dyn std::error::Error + Send + Sync>>,
+    },
+    /// 로컬 파일 읽기/쓰기 실패 (전송 중 로컬 쪽)
+    LocalIo {
+        path: PathBuf,
+        source: std::io::Error,
+    },
+    /// 개인키 파일 로딩 실패 (파일 없음, 포맷 오류, 복호화 실패)
+    KeyLoad {
+        path: PathBuf,
+        message: String,
+    },
+    /// 암호화된 개인키인데 passphrase가 주어지지 않음
+    KeyEncrypted(PathBuf),
+    /// ssh-agent 접속/통신 실패, 또는 사용할 키 없음
+    Agent(String),
+    /// 호스트키 검증 실패 (미등록 + Strict, 키 변경, @revoked, 사용자 거부)
+    HostKey {
+        host: String,
+        fingerprint: String,
+        message: String,
+    },
+    /// 연결 단계 타임아웃 (state: 만료 시점의 상태, after: 설정된 제한 시간)
+    Timeout {
+        state: ConnectionState,
+        after: std::time::Duration,
+    },
+    /// 알고리즘 설정 오류 / 서버와 공통 알고리즘 없음
+    Algorithm(String),
+    /// 접속 설정 오류 (User 미지정 + 로컬 사용자명도 알 수 없음 등)
+    Config(String),
+    /// 프록시 연결/협상 실패 (kind: 실패 단계, source: 프록시와의 io 에러)
+    Proxy {
+        kind: ProxyErrorKind,
+        message: String,
+        source: Option<std::io::Error>,
+    },
+    /// 접속 프로필 저장소 / 가져오기 오류 (파일 형식, 없는 프로필)
+    Profile(String),
+    /// 자격 증명 볼트 오류 (파일 형식, 잘못된 마스터 패스워드, 변조)
+    Vault(String),
+    /// 볼트가 잠겨 있음 (unlock 전, 또는 유휴 시간 초과로 자동 잠금)
+    VaultLocked,
+    /// 점프 호스트 단계 실패 (hop: 1부터, addr: "host:port", source: 원인)
+    JumpHost {
+        hop: usize,
+        addr: String,
+        source: Box<Error>,
+    },
 }
 
 impl fmt::Display for Error {
@@ -26,13 +87,178 @@ impl fmt::Display for Error {
             Error::Io(e)                          => write!(f, "IO error: {}", e),
             Error::InvalidTransition { from, to } => write!(f, "Invalid state transition: {:?} → {:?}", from, to),
             Error::Protocol(s)                    => write!(f, "Protocol error: {}", s),
-            Error::Auth(s)                        => write!(f, "Auth failed: {}", s),
-            Error::Sftp(s)                        => write!(f, "SFTP error: {}", s),
+            Error::Auth { message, attempts }     => {
+                write!(f, "Auth failed: {}", message)?;
+                for a in attempts {
+                    write!(f, "\n  - {}", a)?;
+                }
+                Ok(())
+            }
+            Error::Sftp { path: Some(path), message, .. } => write!(f, "SFTP error ({}): {}", path, message),
+            Error::Sftp { path: None, message, .. }       => write!(f, "SFTP error: {}", message),
+            Error::LocalIo { path, source }       => write!(f, "Local IO error ({}): {}", path.display(), source),
+            Error::KeyLoad { path, message }      => write!(f, "Key load failed ({}): {}", path.display(), message),
+            Error::KeyEncrypted(path)             => write!(f, "Key is encrypted, passphrase required: {}", path.display()),
+            Error::Agent(s)                       => write!(f, "ssh-agent error: {}", s),
+            Error::HostKey { host, fingerprint, message } => {
+                write!(f, "Host key verification failed for {} ({}): {}", host, fingerprint, message)
+            }
+            Error::Timeout { state, after }       => write!(f, "Timed out in {:?} after {:.1}s", state, after.as_secs_f64()),
+            Error::Algorithm(s)                   => write!(f, "Algorithm negotiation failed: {}", s),
+            Error::Config(s)                      => write!(f, "Config error: {}", s),
+            Error::Proxy { message, .. }          => write!(f, "Proxy error: {}", message),
+            Error::Profile(s)                     => write!(f, "Profile error: {}", s),
+            Error::Vault(s)                       => write!(f, "Vault error: {}", s),
+            Error::VaultLocked                    => write!(f, "Vault is locked"),
+            Error::JumpHost { hop, addr, source } => write!(f, "Jump host #{} ({}): {}", hop, addr, source),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Io(e)                   => Some(e),
+            Error::LocalIo { source, .. }  => Some(source),
+            Error::Proxy { source, .. }    => source.as_ref().map(|e| e as &(dyn std::error::Error + 'static)),
+            Error::Sftp { source, .. }     => source.as_deref().map(|e| e as &(dyn std::error::Error + 'static)),
+            Error::JumpHost { source, .. } => Some(source.as_ref()),
+            _ => None,
+        }
+    }
+}
+
+impl Error {
+    /// 시도 내역 없이 인증 에러 생성 (단일 메서드 내부 실패용)
+    pub fn auth(message: impl Into<String>) -> Self {
+        Error::Auth { message: message.into(), attempts: Vec::new() }
+    }
+
+    /// 다시 시도하면 성공할 수 있는 에러인지 (전송 계층 끊김 / 거부 / 시간 초과)
+    ///
+    /// Io는 네트워크 쪽 ErrorKind만 true (디스크 부족, 권한 없음 등 로컬 에러는 false)
+    /// 프록시 에러는 프록시 연결 / 통신 실패만 true (프록시 인증 거부, CONNECT 실패, ProxyCommand 실행 실패는 false)
+    /// 인증 실패, 호스트키 거부, 키 로딩 실패, 알고리즘 불일치, 프로토콜 위반은 재시도해도 같은 결과 → false
+    /// SFTP 에러는 연결 끊김 / 응답 시간 초과만 true (파일 없음, 권한 없음 등은 false)
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            Error::Io(e) => is_transport_kind(e.kind()),
+            Error::Proxy { kind, .. } => *kind == ProxyErrorKind::Io,
+            Error::Timeout { .. } => true,
+            Error::Sftp { kind, .. } => matches!(kind, SftpErrorKind::ConnectionLost | SftpErrorKind::Timeout),
+            Error::JumpHost { source, .. } => source.is_retryable(),
+            _ => false,
+        }
+    }
+
+    /// SFTP 에러 종류 (SFTP 에러가 아니면 None)
+    pub fn sftp_kind(&self) -> Option<SftpErrorKind> {
+        match self {
+            Error::Sftp { kind, .. } => Some(*kind),
+            _ => None,
+        }
+    }
+
+    /// russh-sftp 요청 에러 → Error::Sftp (path: 요청 대상 리모트 경로)
+    pub(crate) fn sftp(path: &str, e: RusshSftpError) -> Self {
+        match Error::from(e) {
+            Error::Sftp { kind, message, source, .. } => Error::Sftp { kind, path: Some(path.to_string()), message, source },
+            e => e,
+        }
+    }
+
+    /// 열린 리모트 파일 읽기/쓰기 에러 → Error::Sftp (io::ErrorKind로 분류)
+    ///
+    /// russh-sftp File은 닫힌 채널을 BrokenPipe로, 그 외 원인은 ErrorKind::Other로 전달 → Failure
+    pub(crate) fn remote_io(path: &str, e: std::io::Error) -> Self {
+        let kind = match e.kind() {
+            std::io::ErrorKind::TimedOut => SftpErrorKind::Timeout,
+            kind if is_transport_kind(kind) => SftpErrorKind::ConnectionLost,
+            _ => SftpErrorKind::Failure,
+        };
+        Error::Sftp { kind, path: Some(path.to_string()), message: e.to_string(), source: Some(Box::new(e)) }
+    }
+
+    /// 프록시 협상 에러 (io 원인 없음)
+    pub(crate) fn proxy(kind: ProxyErrorKind, message: impl Into<String>) -> Self {
+        Error::Proxy { kind, message: message.into(), source: None }
+    }
+
+    /// 프록시와의 연결 / 통신 io 에러 → Error::Proxy { kind: Io }
+    pub(crate) fn proxy_io(context: impl fmt::Display, e: std::io::Error) -> Self {
+        Error::Proxy { kind: ProxyErrorKind::Io, message: format!("{}: {}", context, e), source: Some(e) }
+    }
+
+    /// 로컬 파일 에러 → Error::LocalIo
+    pub(crate) fn local_io(path: &str, e: std::io::Error) -> Self {
+        Error::LocalIo { path: PathBuf::from(path), source: e }
+    }
+}
+
+/// SFTP 요청 실패 종류 (SSH_FX_* 상태 코드 + 클라이언트에서 판단한 끊김 / 시간 초과)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SftpErrorKind {
+    /// SSH_FX_NO_SUCH_FILE
+    NoSuchFile,
+    /// SSH_FX_PERMISSION_DENIED
+    PermissionDenied,
+    /// SSH_FX_FAILURE (서버가 구체적인 이유를 주지 않음: 디렉토리가 비어 있지 않음, 이미 존재 등)
+    Failure,
+    /// SSH_FX_BAD_MESSAGE
+    BadMessage,
+    /// SSH_FX_OP_UNSUPPORTED
+    OpUnsupported,
+    /// 채널 / 연결 끊김 (SSH_FX_NO_CONNECTION, SSH_FX_CONNECTION_LOST, 닫힌 채널)
+    ConnectionLost,
+    /// 서버 응답 시간 초과
+    Timeout,
+    /// 그 외 (예상하지 못한 응답, limits 초과 등)
+    Other,
+}
+
+impl fmt::Display for SftpErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SftpErrorKind::NoSuchFile       => write!(f, "no such file"),
+            SftpErrorKind::PermissionDenied => write!(f, "permission denied"),
+            SftpErrorKind::Failure          => write!(f, "failure"),
+            SftpErrorKind::BadMessage       => write!(f, "bad message"),
+            SftpErrorKind::OpUnsupported    => write!(f, "operation unsupported"),
+            SftpErrorKind::ConnectionLost   => write!(f, "connection lost"),
+            SftpErrorKind::Timeout          => write!(f, "timeout"),
+            SftpErrorKind::Other            => write!(f, "other"),
         }
     }
 }
 
-impl std::error::Error for Error {}
+/// 프록시 실패 종류
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProxyErrorKind {
+    /// 프록시 연결 / 통신 실패 (연결 거부, 끊김 등, 재시도 대상)
+    Io,
+    /// ProxyCommand 실행 실패
+    Command,
+    /// 프록시 인증 실패 (SOCKS5 username/password 거부, HTTP 407)
+    Auth,
+    /// 프록시가 대상 호스트에 연결하지 못함 (SOCKS5 CONNECT 실패, HTTP 200 이외 응답)
+    Connect,
+    /// 프록시 응답 형식 오류 / 지원하지 않는 방식 / 표현할 수 없는 요청
+    Protocol,
+}
+
+impl From<StatusCode> for SftpErrorKind {
+    fn from(code: StatusCode) -> Self {
+        match code {
+            StatusCode::NoSuchFile       => SftpErrorKind::NoSuchFile,
+            StatusCode::PermissionDenied => SftpErrorKind::PermissionDenied,
+            StatusCode::Failure          => SftpErrorKind::Failure,
+            StatusCode::BadMessage       => SftpErrorKind::BadMessage,
+            StatusCode::OpUnsupported    => SftpErrorKind::OpUnsupported,
+            StatusCode::NoConnection | StatusCode::ConnectionLost => SftpErrorKind::ConnectionLost,
+            StatusCode::Ok | StatusCode::Eof => SftpErrorKind::Other,
+        }
+    }
+}
 
 impl From<std::io::Error> for Error {
     fn from(e: std::io::Error) -> Self {
@@ -40,4 +266,59 @@ impl From<std::io::Error> for Error {
     }
 }
 
+/// russh-sftp 에러 → Error::Sftp (경로 없음, 경로가 있으면 Error::sftp 사용)
+impl From<RusshSftpError> for Error {
+    fn from(e: RusshSftpError) -> Self {
+        let (kind, message) = match &e {
+            RusshSftpError::Status(status) => {
+                let message = if status.error_message.is_empty() {
+                    status.status_code.to_string()
+                } else {
+                    status.error_message.clone()
+                };
+                (SftpErrorKind::from(status.status_code), message)
+            }
+            RusshSftpError::IO(_)   => (SftpErrorKind::ConnectionLost, e.to_string()),
+            RusshSftpError::Timeout => (SftpErrorKind::Timeout, "no response from server".to_string()),
+            // UnexpectedBehavior(닫힌 채널에 보낸 요청 포함), Limited, UnexpectedPacket
+            // 연결 끊김은 ConnectionState(SftpSession::is_connected)로 판단
+            _ => (SftpErrorKind::Other, e.to_string()),
+        };
+        Error::Sftp { kind, path: None, message, source: Some(Box::new(e)) }
+    }
+}
+
+/// russh Handler의 에러 타입으로 쓰기 위한 변환 (check_server_key에서 Error::HostKey 전달)
+///
+/// 전송 계층 에러(소켓 에러, 끊김, 시간 초과, 연결 거부)는 Error::Io → is_retryable() true
+/// 그 외(키 교환 실패, 서명 오류 등 프로토콜 에러)는 Error::Protocol
+impl From<russh::Error> for Error {
+    fn from(e: russh::Error) -> Self {
+        use std::io::ErrorKind;
+        let kind = match e {
+            russh::Error::IO(e) => return Error::Io(e),
+            russh::Error::Disconnect | russh::Error::SendError => ErrorKind::ConnectionAborted,
+            russh::Error::HUP => ErrorKind::UnexpectedEof,
+            russh::Error::ConnectionTimeout
+            | russh::Error::KeepaliveTimeout
+            | russh::Error::InactivityTimeout
+            | russh::Error::Elapsed(_) => ErrorKind::TimedOut,
+            russh::Error::ChannelOpenFailure(russh::ChannelOpenFailure::ConnectFailed) => ErrorKind::ConnectionRefused,
+            russh::Error::NoCommonAlgo { .. } => return Error::Algorithm(e.to_string()),
+            _ => return Error::Protocol(e.to_string()),
+        };
+        Error::Io(std::io::Error::new(kind, e))
+    }
+}
+
+/// 네트워크 / 연결 수준 io 에러 종류인지 (재시도 대상)
+fn is_transport_kind(kind: std::io::ErrorKind) -> bool {
+    use std::io::ErrorKind::*;
+    matches!(
+        kind,
+        ConnectionRefused | ConnectionReset | ConnectionAborted | NotConnected | BrokenPipe
+            | UnexpectedEof | TimedOut | HostUnreachable | NetworkUnreachable | NetworkDown | AddrNotAvailable
+    )
+}
+
 pub type Result<T> = std::result::Result<T, Error>;
@@ -0,0 +1,353 @@
+// miniSFTP Handshake Tap
+// author: kodeholic (powered by Claude)
+//
+// 스트림을 감싸 서버가 보낸 첫 메시지를 엿봄 (읽기만, 데이터는 그대로 russh / russh-sftp로 전달)
+// russh / russh-sftp가 노출하지 않는 정보를 얻기 위함
+//
+// SSH 전송 스트림 (tap)
+//   "SSH-2.0-OpenSSH_9.6\r\n"   ← 식별 문자열 (앞의 기타 줄은 무시)
+//   uint32 packet_length, byte padding_length, payload(SSH_MSG_KEXINIT = 20) ...
+//   첫 KEXINIT까지만 읽음 (이후 패킷은 암호화됨)
+//   서버 식별 문자열을 받는 즉시 on_server_id 호출 → KeyExchange 상태 전이
+//
+// SFTP 채널 스트림 (sftp_tap)
+//   uint32 length, byte SSH_FXP_VERSION(2), uint32 version, (string name, string data)*
+
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll};
+
+use tokio::io::{AsyncRead, AsyncWrite, ReadBuf};
+
+use crate::algorithms::{NegotiatedAlgorithms, ServerAlgorithms};
+use crate::proxy::ProxyStream;
+use crate::state::ConnectionState;
+
+const SSH_MSG_KEXINIT: u8 = 20;
+const SSH_FXP_VERSION: u8 = 2;
+/// 찾는 메시지가 이 크기 안에 없으면 포기
+const MAX_CAPTURE: usize = 64 * 1024;
+
+/// SSH 핸드셰이크 / 인증 중 수집한 서버 정보 (진행 중에는 일부만 채워짐)
+#[derive(Debug, Clone, Default)]
+pub(crate) struct ServerHello {
+    /// 서버 식별 문자열 ("SSH-2.0-...")
+    pub version: Option<String>,
+    /// 서버 KEXINIT 알고리즘 목록
+    pub algorithms: Option<ServerAlgorithms>,
+    /// 협상 결과 (핸드셰이크 성공 후 계산)
+    pub negotiated: Option<NegotiatedAlgorithms>,
+    /// 서버 호스트키 (check_server_key에서 기록)
+    pub host_key_type: Option<String>,
+    pub fingerprint: Option<String>,
+    /// SSH_MSG_USERAUTH_BANNER (auth_banner에서 기록, 여러 번 오면 이어붙임)
+    pub banner: Option<String>,
+}
+
+impl ServerHello {
+    /// 키 교환 완료 상태 (알 수 없는 항목은 빈 문자열)
+    pub fn encrypted_state(&self) -> ConnectionState {
+        let negotiated = self.negotiated.as_ref();
+        ConnectionState::Encrypted {
+            kex: negotiated.map(|n| n.kex.clone()).unwrap_or_default(),
+            cipher: negotiated.map(|n| n.cipher.clone()).unwrap_or_default(),
+            host_key_type: self.host_key_type.clone().unwrap_or_default(),
+            fingerprint: self.fingerprint.clone().unwrap_or_default(),
+        }
+    }
+}
+
+/// SSH_FXP_VERSION 내용
+#[derive(Debug, Clone, Default)]
+pub(crate) struct SftpHello {
+    pub version: u32,
+    /// (이름, 데이터) 서버가 보낸 순서대로
+    pub extensions: Vec<(String, String)>,
+}
+
+/// 서버 식별 문자열 수신 콜백
+pub(crate) type OnServerId = Box<dyn FnOnce(&str) + Send>;
+
+/// SSH 전송 스트림을 감싸고 ServerHello 공유 핸들 반환
+pub(crate) fn tap(stream: Box<dyn ProxyStream>, on_server_id: OnServerId) -> (Box<dyn ProxyStream>, Arc<Mutex<ServerHello>>) {
+    let hello = Arc::new(Mutex::new(ServerHello::default()));
+    let shared = hello.clone();
+    let mut on_server_id = Some(on_server_id);
+
+    let inspect = move |buf: &mut Vec<u8>| {
+        let mut hello = shared.lock().unwrap();
+        while hello.version.is_none() {
+            let Some(end) = buf.iter().position(|&b| b == b'\n') else { return false };
+            let line: Vec<u8> = buf.drain(..=end).collect();
+            let line = String::from_utf8_lossy(&line).trim_end().to_string();
+            if line.starts_with("SSH-") {
+                if let Some(on_server_id) = on_server_id.take() {
+                    on_server_id(&line);
+                }
+                hello.version = Some(line);
+            }
+        }
+
+        let Some(payload) = ssh_packet(buf) else { return false };
+        if payload.first() == Some(&SSH_MSG_KEXINIT) {
+            hello.algorithms = parse_kexinit(payload);
+        }
+        true
+    };
+    (Box::new(Tap::new(stream, inspect)), hello)
+}
+
+/// SFTP 채널 스트림을 감싸고 SSH_FXP_VERSION 공유 핸들 반환
+pub(crate) fn sftp_tap(stream: Box<dyn ProxyStream>) -> (Box<dyn ProxyStream>, Arc<Mutex<Option<SftpHello>>>) {
+    let hello = Arc::new(Mutex::new(None));
+    let shared = hello.clone();
+
+    let inspect = move |buf: &mut Vec<u8>| {
+        if buf.len() < 4 {
+            return false;
+        }
+        let len = u32::from_be_bytes([buf[0], buf[1], buf[2], buf[3]]) as usize;
+        let Some(payload) = len.checked_add(4).and_then(|end| buf.get(4..end)) else { return false };
+        if payload.first() == Some(&SSH_FXP_VERSION) {
+            *shared.lock().unwrap() = parse_sftp_version(payload);
+        }
+        true
+    };
+    (Box::new(Tap::new(stream, inspect)), hello)
+}
+
+/// 읽은 바이트를 inspect에 넘기는 스트림 (inspect가 true를 반환하면 이후로는 그대로 통과)
+struct Tap<F> {
+    inner: Box<dyn ProxyStream>,
+    inspect: F,
+    buf: Vec<u8>,
+    done: bool,
+}
+
+impl<F: FnMut(&mut Vec<u8>) -> bool> Tap<F> {
+    fn new(inner: Box<dyn ProxyStream>, inspect: F) -> Self {
+        Self { inner, inspect, buf: Vec::new(), done: false }
+    }
+
+    fn feed(&mut self, data: &[u8]) {
+        self.buf.extend_from_slice(data);
+        self.done = (self.inspect)(&mut self.buf) || self.buf.len() > MAX_CAPTURE;
+        if self.done {
+            self.buf = Vec::new();
+        }
+    }
+}
+
+impl<F: FnMut(&mut Vec<u8>) -> bool + Unpin> AsyncRead for Tap<F> {
+    fn poll_read(mut self: Pin<&mut Self>, cx: &mut Context<'_>, buf: &mut ReadBuf<'_>) -> Poll<std::io::Result<()>> {
+        let before = buf.filled().len();
+        let result = Pin::new(&mut self.inner).poll_read(cx, buf);
+        if !self.done && matches!(result, Poll::Ready(Ok(()))) {
+            let data = buf.filled()[before..].to_vec();
+            self.feed(&data);
+        }
+        result
+    }
+}
+
+impl<F: Unpin> AsyncWrite for Tap<F> {
+    fn poll_write(mut self: Pin<&mut Self>, cx: &mut Context<'_>, buf: &[u8]) -> Poll<std::io::Result<usize>> {
+        Pin::new(&mut self.inner).poll_write(cx, buf)
+    }
+
+    fn poll_flush(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<std::io::Result<()>> {
+        Pin::new(&mut self.inner).poll_flush(cx)
+    }
+
+    fn poll_shutdown(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<std::io::Result<()>> {
+        Pin::new(&mut self.inner).poll_shutdown(cx)
+    }
+}
+
+/// 평문 SSH 바이너리 패킷의 payload (아직 다 받지 못했거나 길이가 맞지 않으면 None)
+fn ssh_packet(buf: &[u8]) -> Option<&[u8]> {
+    let len = u32::from_be_bytes(buf.get(..4)?.try_into().ok()?) as usize;
+    let packet = buf.get(4..len.checked_add(4)?)?;
+    let padding = *packet.first()? as usize;
+    Some(packet.get(1..len.saturating_sub(padding)).unwrap_or_default())
+}
+
+/// KEXINIT payload: byte 20, cookie[16], name-list × 10, boolean, uint32
+///
+/// name-list 순서: kex, host key, cipher c→s, cipher s→c, mac c→s, mac s→c, comp c→s, comp s→c, lang × 2
+fn parse_kexinit(payload: &[u8]) -> Option<ServerAlgorithms> {
+    let mut pos = 17;
+    let mut lists = Vec::with_capacity(10);
+    for _ in 0..10 {
+        let value = read_string(payload, &mut pos)?;
+        lists.push(
+            String::from_utf8_lossy(value)
+                .split(',')
+                .filter(|n| !n.is_empty())
+                .map(str::to_string)
+                .collect::<Vec<_>>(),
+        );
+    }
+
+    let mut lists = lists.into_iter();
+    let mut next = || lists.next().unwrap_or_default();
+    let kex = next();
+    let host_key = next();
+    let cipher = next();
+    next();
+    let mac = next();
+    next();
+    let compression = next();
+    Some(ServerAlgorithms { kex, host_key, cipher, mac, compression })
+}
+
+/// SSH_FXP_VERSION payload: byte 2, uint32 version, (string name, string data)*
+fn parse_sftp_version(payload: &[u8]) -> Option<SftpHello> {
+    let version = u32::from_be_bytes(payload.get(1..5)?.try_into().ok()?);
+    let mut pos = 5;
+    let mut extensions = Vec::new();
+    while pos < payload.len() {
+        let name = read_string(payload, &mut pos)?;
+        let data = read_string(payload, &mut pos)?;
+        extensions.push((String::from_utf8_lossy(name).to_string(), String::from_utf8_lossy(data).to_string()));
+    }
+    Some(SftpHello { version, extensions })
+}
+
+/// uint32 길이 + 바이트열 (길이가 남은 데이터보다 크면 None)
+fn read_string<'a>(buf: &'a [u8], pos: &mut usize) -> Option<&'a [u8]> {
+    let start = pos.checked_add(4)?;
+    let len = u32::from_be_bytes(buf.get(*pos..start)?.try_into().ok()?) as usize;
+    let end = start.checked_add(len)?;
+    let value = buf.get(start..end)?;
+    *pos = end;
+    Some(value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn string(value: &str) -> Vec<u8> {
+        let mut out = (value.len() as u32).to_be_bytes().to_vec();
+        out.extend_from_slice(value.as_bytes());
+        out
+    }
+
+    /// byte 20, cookie, name-list × 10, first_kex_packet_follows, reserved
+    fn kexinit(lists: [&str; 10]) -> Vec<u8> {
+        let mut payload = vec![SSH_MSG_KEXINIT];
+        payload.extend_from_slice(&[0xAA; 16]);
+        for list in lists {
+            payload.extend(string(list));
+        }
+        payload.push(0);
+        payload.extend_from_slice(&0u32.to_be_bytes());
+        payload
+    }
+
+    /// uint32 packet_length, byte padding_length, payload, padding
+    fn packet(payload: &[u8], padding: usize) -> Vec<u8> {
+        let mut out = ((1 + payload.len() + padding) as u32).to_be_bytes().to_vec();
+        out.push(padding as u8);
+        out.extend_from_slice(payload);
+        out.extend(std::iter::repeat_n(0, padding));
+        out
+    }
+
+    const LISTS: [&str; 10] = [
+        "curve25519-sha256,kex-strict-s-v00@openssh.com",
+        "ssh-ed25519,rsa-sha2-512",
+        "chacha20-poly1305@openssh.com,aes128-ctr",
+        "aes256-ctr",
+        "hmac-sha2-256",
+        "hmac-sha2-512",
+        "none,zlib@openssh.com",
+        "none",
+        "",
+        "",
+    ];
+
+    #[test]
+    fn kexinit_lists() {
+        let algorithms = parse_kexinit(&kexinit(LISTS)).unwrap();
+        assert_eq!(algorithms.kex, ["curve25519-sha256", "kex-strict-s-v00@openssh.com"]);
+        assert_eq!(algorithms.host_key, ["ssh-ed25519", "rsa-sha2-512"]);
+        // cipher / mac / compression은 클라이언트 → 서버 방향
+        assert_eq!(algorithms.cipher, ["chacha20-poly1305@openssh.com", "aes128-ctr"]);
+        assert_eq!(algorithms.mac, ["hmac-sha2-256"]);
+        assert_eq!(algorithms.compression, ["none", "zlib@openssh.com"]);
+    }
+
+    #[test]
+    fn kexinit_truncated_or_oversized() {
+        let payload = kexinit(LISTS);
+        // 마지막 name-list 전까지만 받음
+        for end in [0, 1, 17, 20, payload.len() - 10] {
+            assert!(parse_kexinit(&payload[..end]).is_none(), "{}", end);
+        }
+        // 첫 name-list 길이가 데이터보다 큼
+        let mut oversized = payload.clone();
+        oversized[17..21].copy_from_slice(&u32::MAX.to_be_bytes());
+        assert!(parse_kexinit(&oversized).is_none());
+    }
+
+    #[test]
+    fn ssh_packet_payload() {
+        let payload = kexinit(LISTS);
+        let buf = packet(&payload, 7);
+        assert_eq!(ssh_packet(&buf), Some(&payload[..]));
+        // 다음 패킷이 이어 붙어 있어도 이 패킷만
+        let mut two = buf.clone();
+        two.extend(packet(b"next", 4));
+        assert_eq!(ssh_packet(&two), Some(&payload[..]));
+    }
+
+    #[test]
+    fn ssh_packet_truncated_or_oversized() {
+        let buf = packet(&kexinit(LISTS), 7);
+        assert!(ssh_packet(&buf[..3]).is_none());
+        assert!(ssh_packet(&buf[..buf.len() - 1]).is_none());
+        assert!(ssh_packet(&[0xFF, 0xFF, 0xFF, 0xFF, 4, SSH_MSG_KEXINIT]).is_none());
+        assert!(ssh_packet(&[0, 0, 0, 0]).is_none());
+        // padding_length가 packet_length보다 커도 패닉 없이 빈 payload
+        assert_eq!(ssh_packet(&[0, 0, 0, 2, 200, 20]), Some(&[][..]));
+    }
+
+    fn sftp_version(version: u32, extensions: &[(&str, &str)]) -> Vec<u8> {
+        let mut payload = vec![SSH_FXP_VERSION];
+        payload.extend_from_slice(&version.to_be_bytes());
+        for (name, data) in extensions {
+            payload.extend(string(name));
+            payload.extend(string(data));
+        }
+        payload
+    }
+
+    #[test]
+    fn sftp_version_extensions() {
+        let payload = sftp_version(3, &[("posix-rename@openssh.com", "1"), ("statvfs@openssh.com", "2")]);
+        let hello = parse_sftp_version(&payload).unwrap();
+        assert_eq!(hello.version, 3);
+        assert_eq!(hello.extensions, [
+            ("posix-rename@openssh.com".to_string(), "1".to_string()),
+            ("statvfs@openssh.com".to_string(), "2".to_string()),
+        ]);
+        assert!(parse_sftp_version(&sftp_version(3, &[])).unwrap().extensions.is_empty());
+    }
+
+    #[test]
+    fn sftp_version_truncated_or_oversized() {
+        let payload = sftp_version(3, &[("posix-rename@openssh.com", "1")]);
+        for end in [0, 1, 4, 7, payload.len() - 1] {
+            assert!(parse_sftp_version(&payload[..end]).is_none(), "{}", end);
+        }
+        let mut oversized = payload.clone();
+        oversized[5..9].copy_from_slice(&u32::MAX.to_be_bytes());
+        assert!(parse_sftp_version(&oversized).is_none());
+        // 이름만 있고 data가 없음
+        let mut missing = sftp_version(3, &[]);
+        missing.extend(string("hardlink@openssh.com"));
+        assert!(parse_sftp_version(&missing).is_none());
+    }
+}
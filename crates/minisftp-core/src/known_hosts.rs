@@ -0,0 +1,324 @@
+// miniSFTP known_hosts 검증
+// author: kodeholic (powered by Claude)
+//
+// ~/.ssh/known_hosts (OpenSSH 포맷) 조회 / 추가
+//
+// 라인 포맷: [@marker] hostpatterns keytype base64key [comment]
+// - 호스트 패턴 : host, *.example.com, !bad.example.com, [host]:2222 (포트 22 이외)
+// - 해시 호스트 : |1|base64(salt)|base64(HMAC-SHA1(salt, host)) (HashKnownHosts yes)
+// - @revoked        : 해당 키는 어떤 정책에서도 허용하지 않음
+// - @cert-authority : 호스트 인증서 서명용 CA 키
+//   호스트 인증서 검증은 미지원 → CA로만 등록된 호스트는 CertAuthority (Unknown으로 취급하지 않음)
+//
+// 검증 정책은 config::HostKeyPolicy, 사용자 확인은 HostKeyPrompter trait
+
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use data_encoding::BASE64;
+use hmac::{Hmac, Mac};
+use russh::keys::{HashAlg, PublicKey, PublicKeyBase64};
+use sha1::Sha1;
+
+use crate::error::Result;
+use crate::utils::wildcard_match;
+
+/// known_hosts 조회 결과
+#[derive(Debug, Clone, PartialEq)]
+pub enum HostKeyStatus {
+    /// 일치하는 키가 있음
+    Known { line: usize },
+    /// 해당 호스트의 키가 없음 (최초 접속)
+    Unknown,
+    /// 해당 호스트에 같은 타입의 다른 키가 등록되어 있음 (MITM 가능성)
+    Changed { line: usize },
+    /// @revoked로 폐기된 키
+    Revoked { line: usize },
+    /// 해당 호스트가 @cert-authority로만 등록되어 있음 (호스트 인증서 검증은 미지원 → 접속 거부)
+    CertAuthority { line: usize },
+}
+
+/// 사용자 확인 / 표시용 호스트키 정보
+#[derive(Debug, Clone)]
+pub struct HostKeyInfo {
+    pub host: String,
+    pub port: u16,
+    pub key_type: String,
+    /// "SHA256:..." (ssh-keygen -l 형식)
+    pub fingerprint: String,
+    pub status: HostKeyStatus,
+}
+
+/// 처음 보는 호스트키를 신뢰할지 사용자에게 확인 (HostKeyPolicy::Ask)
+///
+/// CLI: fingerprint 출력 후 yes/no 입력
+/// Tauri: 다이얼로그 표시
+/// true 반환 → 접속 진행 + known_hosts에 추가
+pub trait HostKeyPrompter: Send + Sync {
+    fn confirm(&self, info: &HostKeyInfo) -> bool;
+}
+
+/// 기본 known_hosts 경로 (~/.ssh/known_hosts)
+pub fn default_path() -> Option<PathBuf> {
+    home::home_dir().map(|h| h.join(".ssh").join("known_hosts"))
+}
+
+/// known_hosts에 기록되는 호스트 이름 (포트 22: "host", 그 외: "[host]:port")
+pub fn host_entry_name(host: &str, port: u16) -> String {
+    if port == 22 { host.to_string() } else { format!("[{}]:{}", host, port) }
+}
+
+/// known_hosts 파일에서 호스트키 조회
+///
+/// 파일이 없으면 Unknown
+/// @revoked > Known > Changed > CertAuthority 순으로 우선
+/// 다른 타입의 키만 등록되어 있으면 Unknown (예: ssh-rsa만 등록된 호스트가 ed25519로 협상, OpenSSH와 같음)
+pub fn check(path: &Path, host: &str, port: u16, key: &PublicKey) -> Result<HostKeyStatus> {
+    let content = match std::fs::read_to_string(path) {
+        Ok(c) => c,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(HostKeyStatus::Unknown),
+        Err(e) => return Err(e.into()),
+    };
+
+    let name = host_entry_name(host, port).to_lowercase();
+    let mut known   = None;
+    let mut changed = None;
+    let mut ca      = None;
+
+    for (idx, line) in content.lines().enumerate() {
+        let line_no = idx + 1;
+        let Some(entry) = parse_line(line) else { continue };
+        if !hosts_match(entry.hosts, &name) {
+            continue;
+        }
+        // 지원하지 않는 키 타입(sk-* 등)은 비교 대상에서 제외
+        let Ok(entry_key) = russh::keys::parse_public_key_base64(entry.key_base64) else {
+            tracing::debug!("[known_hosts] line {}: unsupported key type {}", line_no, entry.key_type);
+            continue;
+        };
+
+        match entry.marker {
+            Some(Marker::Revoked) if entry_key.key_data() == key.key_data() => {
+                return Ok(HostKeyStatus::Revoked { line: line_no });
+            }
+            Some(Marker::CertAuthority) => { ca.get_or_insert(line_no); }
+            Some(Marker::Revoked) => {}
+            None if entry_key.key_data() == key.key_data() => { known.get_or_insert(line_no); }
+            None if entry_key.algorithm() == key.algorithm() => { changed.get_or_insert(line_no); }
+            None => {}
+        }
+    }
+
+    Ok(match (known, changed, ca) {
+        (Some(line), _, _)       => HostKeyStatus::Known { line },
+        (None, Some(line), _)    => HostKeyStatus::Changed { line },
+        (None, None, Some(line)) => HostKeyStatus::CertAuthority { line },
+        (None, None, None)       => HostKeyStatus::Unknown,
+    })
+}
+
+/// known_hosts에 호스트키 추가 (평문 호스트명, 파일/디렉토리 없으면 생성)
+pub fn learn(path: &Path, host: &str, port: u16, key: &PublicKey) -> Result<()> {
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir)?;
+    }
+
+    let mut file = std::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)?;
+
+    // 기존 마지막 줄에 개행이 없으면 보정
+    let needs_newline = std::fs::read(path)
+        .map(|b| !b.is_empty() && !b.ends_with(b"\n"))
+        .unwrap_or(false);
+    if needs_newline {
+        file.write_all(b"\n")?;
+    }
+
+    writeln!(file, "{} {} {}", host_entry_name(host, port), key_type_name(key), key.public_key_base64())?;
+    Ok(())
+}
+
+/// known_hosts 표기용 키 타입 (RSA는 서명 해시와 무관하게 "ssh-rsa")
+pub fn key_type_name(key: &PublicKey) -> String {
+    key.algorithm().as_str().to_string()
+}
+
+/// "SHA256:..." 형식 fingerprint
+pub fn fingerprint(key: &PublicKey) -> String {
+    key.fingerprint(HashAlg::Sha256).to_string()
+}
+
+// ── 파싱 ─────────────────────────────────────────────────────────────────────
+
+enum Marker {
+    Revoked,
+    CertAuthority,
+}
+
+struct Entry<'a> {
+    marker: Option<Marker>,
+    hosts: &'a str,
+    key_type: &'a str,
+    key_base64: &'a str,
+}
+
+fn parse_line(line: &str) -> Option<Entry<'_>> {
+    let line = line.trim();
+    if line.is_empty() || line.starts_with('#') {
+        return None;
+    }
+
+    let mut fields = line.split_whitespace();
+    let mut first  = fields.next()?;
+    let marker = match first {
+        "@revoked"        => Some(Marker::Revoked),
+        "@cert-authority" => Some(Marker::CertAuthority),
+        m if m.starts_with('@') => return None, // 알 수 없는 마커 → 라인 무시 (OpenSSH 동작)
+        _ => None,
+    };
+    if marker.is_some() {
+        first = fields.next()?;
+    }
+
+    Some(Entry {
+        marker,
+        hosts: first,
+        key_type: fields.next()?,
+        key_base64: fields.next()?,
+    })
+}
+
+/// 호스트 패턴 목록 매칭 (쉼표 구분, !부정 패턴이 일치하면 라인 전체 불일치)
+fn hosts_match(patterns: &str, name: &str) -> bool {
+    if patterns.starts_with("|1|") {
+        return hashed_match(patterns, name);
+    }
+
+    let mut matched = false;
+    for pattern in patterns.split(',') {
+        match pattern.strip_prefix('!') {
+            Some(negated) if wildcard_match(&negated.to_lowercase(), name) => return false,
+            Some(_) => {}
+            None if wildcard_match(&pattern.to_lowercase(), name) => matched = true,
+            None => {}
+        }
+    }
+    matched
+}
+
+/// |1|salt|hash → HMAC-SHA1(salt, name) == hash
+fn hashed_match(entry: &str, name: &str) -> bool {
+    let mut parts = entry.split('|').skip(2);
+    let (Some(salt), Some(hash)) = (parts.next(), parts.next()) else { return false };
+    let (Ok(salt), Ok(hash)) = (BASE64.decode(salt.as_bytes()), BASE64.decode(hash.as_bytes())) else {
+        return false;
+    };
+    match Hmac::<Sha1>::new_from_slice(&salt) {
+        Ok(mac) => mac.chain_update(name.as_bytes()).verify_slice(&hash).is_ok(),
+        Err(_) => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ED25519_A: &str = "AAAAC3NzaC1lZDI1NTE5AAAAIOaINkJeN/gaB0Jv9/vOFzDrCdBRyURrk+W55lV1jtZu";
+    const ED25519_B: &str = "AAAAC3NzaC1lZDI1NTE5AAAAIHdVUOm+lAgHIL5UEN4T7UV3gHnxSlAooE1yCf56foa2";
+    const ECDSA: &str = "AAAAE2VjZHNhLXNoYTItbmlzdHAyNTYAAAAIbmlzdHAyNTYAAABBBLp4b6v0waeSWb3SPSczSaCnNIlM/yDEcYeF2BQPTMuXG3GoNbTPEA7RlZto3wkX5LgR3zJWD0tXlNpAhRoK8Oo=";
+
+    fn key(base64: &str) -> PublicKey {
+        russh::keys::parse_public_key_base64(base64).unwrap()
+    }
+
+    /// 임시 known_hosts에 content를 쓰고 check (끝나면 remove_file)
+    fn check_with(name: &str, content: &str, host: &str, port: u16, key_base64: &str) -> HostKeyStatus {
+        let path = std::env::temp_dir().join(format!("minisftp-known-hosts-{}-{}", std::process::id(), name));
+        std::fs::write(&path, content).unwrap();
+        let status = check(&path, host, port, &key(key_base64)).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        status
+    }
+
+    #[test]
+    fn hashed_host() {
+        // ssh-keygen -H로 만든 "example.com"
+        let content = format!("|1|SMFh3pzaoUOZpN5ndvKBh6V8XLI=|GtuCBBrvQENE37WeXJKruJDQhHg= ssh-ed25519 {}\n", ED25519_A);
+        assert_eq!(check_with("hashed", &content, "example.com", 22, ED25519_A), HostKeyStatus::Known { line: 1 });
+        assert_eq!(check_with("hashed-case", &content, "EXAMPLE.com", 22, ED25519_A), HostKeyStatus::Known { line: 1 });
+        assert_eq!(check_with("hashed-other", &content, "example.org", 22, ED25519_A), HostKeyStatus::Unknown);
+        assert!(!hashed_match("|1|not base64|x", "example.com"));
+    }
+
+    #[test]
+    fn host_with_port() {
+        let content = format!("[example.com]:2222 ssh-ed25519 {}\n", ED25519_A);
+        assert_eq!(check_with("port", &content, "example.com", 2222, ED25519_A), HostKeyStatus::Known { line: 1 });
+        assert_eq!(check_with("port-22", &content, "example.com", 22, ED25519_A), HostKeyStatus::Unknown);
+        assert_eq!(check_with("port-other", &content, "example.com", 2200, ED25519_A), HostKeyStatus::Unknown);
+        assert_eq!(host_entry_name("example.com", 22), "example.com");
+        assert_eq!(host_entry_name("example.com", 2222), "[example.com]:2222");
+    }
+
+    #[test]
+    fn wildcard_and_negated_patterns() {
+        assert!(hosts_match("*.example.com", "web.example.com"));
+        assert!(hosts_match("db,web?.example.com", "web1.example.com"));
+        assert!(!hosts_match("*.example.com", "example.com"));
+        assert!(hosts_match("*.example.com,!bad.example.com", "good.example.com"));
+        // 부정 패턴이 일치하면 다른 패턴과 상관없이 불일치
+        assert!(!hosts_match("*.example.com,!bad.example.com", "bad.example.com"));
+        assert!(!hosts_match("!bad.example.com", "good.example.com"));
+        assert!(hosts_match("[*.example.com]:2222", "[web.example.com]:2222"));
+
+        let content = format!("*.example.com,!bad.example.com ssh-ed25519 {}\n", ED25519_A);
+        assert_eq!(check_with("negated", &content, "bad.example.com", 22, ED25519_A), HostKeyStatus::Unknown);
+    }
+
+    #[test]
+    fn revoked_wins() {
+        let content = format!(
+            "example.com ssh-ed25519 {a}\n@revoked * ssh-ed25519 {a}\n@revoked * ssh-ed25519 {b}\n",
+            a = ED25519_A, b = ED25519_B,
+        );
+        assert_eq!(check_with("revoked", &content, "example.com", 22, ED25519_A), HostKeyStatus::Revoked { line: 2 });
+        // 다른 키의 @revoked는 영향 없음
+        let content = format!("example.com ssh-ed25519 {a}\n@revoked * ssh-ed25519 {b}\n", a = ED25519_A, b = ED25519_B);
+        assert_eq!(check_with("revoked-other", &content, "example.com", 22, ED25519_A), HostKeyStatus::Known { line: 1 });
+    }
+
+    #[test]
+    fn changed_only_for_same_key_type() {
+        let content = format!("example.com ssh-ed25519 {}\n", ED25519_A);
+        assert_eq!(check_with("changed", &content, "example.com", 22, ED25519_B), HostKeyStatus::Changed { line: 1 });
+        // ed25519만 등록된 호스트가 ecdsa로 협상 → 새 키 (OpenSSH와 같음)
+        assert_eq!(check_with("changed-type", &content, "example.com", 22, ECDSA), HostKeyStatus::Unknown);
+        // 같은 호스트에 일치하는 키가 하나라도 있으면 Known
+        let content = format!("example.com ssh-ed25519 {b}\nexample.com ssh-ed25519 {a}\n", a = ED25519_A, b = ED25519_B);
+        assert_eq!(check_with("changed-known", &content, "example.com", 22, ED25519_A), HostKeyStatus::Known { line: 2 });
+    }
+
+    #[test]
+    fn cert_authority_only() {
+        let content = format!("# CA\n@cert-authority *.example.com ssh-ed25519 {}\n", ED25519_B);
+        assert_eq!(
+            check_with("ca", &content, "web.example.com", 22, ED25519_A),
+            HostKeyStatus::CertAuthority { line: 2 },
+        );
+        assert_eq!(check_with("ca-other", &content, "example.org", 22, ED25519_A), HostKeyStatus::Unknown);
+        // 일반 라인이 있으면 그쪽이 우선
+        let content = format!("{}web.example.com ssh-ed25519 {}\n", content, ED25519_A);
+        assert_eq!(check_with("ca-known", &content, "web.example.com", 22, ED25519_A), HostKeyStatus::Known { line: 3 });
+    }
+
+    #[test]
+    fn learn_then_known() {
+        let path = std::env::temp_dir().join(format!("minisftp-known-hosts-{}-learn", std::process::id()));
+        std::fs::write(&path, "other ssh-ed25519 AAAA").unwrap();
+        learn(&path, "example.com", 2222, &key(ED25519_A)).unwrap();
+        assert_eq!(check(&path, "example.com", 2222, &key(ED25519_A)).unwrap(), HostKeyStatus::Known { line: 2 });
+        std::fs::remove_file(&path).unwrap();
+    }
+}
@@ -1,9 +1,18 @@
 // miniSFTP Core Library (russh 기반)
 // author: kodeholic (powered by Claude)
 
+pub mod algorithms;
+pub mod auth;
 pub mod config;
 pub mod error;
+pub(crate) mod handshake;
+pub mod known_hosts;
+pub mod profile;
+pub(crate) mod proxy;
+pub mod secret;
 pub mod session;
+pub mod ssh_config;
 pub mod sftp;
 pub mod state;
 pub mod utils;
+pub mod vault;
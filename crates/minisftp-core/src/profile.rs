@@ -0,0 +1,510 @@
+// miniSFTP Connection Profiles (사이트 관리자)
+// author: kodeholic (powered by Claude)
+//
+// 이름 붙인 접속 설정 저장소 (FileZilla 사이트 관리자 대응)
+//
+// - 저장 위치: utils::config_dir()/profiles.toml
+// - 파일 형식: TOML ([[profile]] 테이블 배열), Profile serde derive로 읽기/쓰기 (toml 크레이트)
+//   모르는 키는 무시, 값이 없는 항목(None / 빈 배열)은 쓰지 않음
+// - Profile / ProfileAuth는 같은 serde derive로 Tauri 쪽과 JSON으로 주고받음
+// - 패스워드는 저장하지 않음 (ProfileAuth::Password는 "패스워드 사용" 표시, 값은 연결 시 입력)
+//   → 암호화 볼트(vault)에 secret_id()로 저장해 두면 to_connect_config_with_vault()가 채움
+// - 폴더: "Production/Web"처럼 '/'로 중첩, 태그: 자유 문자열
+// - FileZilla sitemanager.xml 가져오기: SFTP 사이트만 (FTP / FTPS는 건너뜀), 폴더 구조 유지 (roxmltree)
+//
+// profiles.toml 예:
+//   [[profile]]
+//   name = "prod-web"
+//   folder = "Production/Web"
+//   tags = ["prod", "web"]
+//   host = "10.0.0.5"
+//   username = "deploy"
+//   auth = ["agent", "publickey:~/.ssh/id_ed25519", "password"]
+//   jump = "admin@bastion"
+//   remote_dir = "/var/www"
+
+use std::borrow::Cow;
+use std::fmt;
+use std::iter::Peekable;
+use std::path::{Path, PathBuf};
+use std::str::{Chars, FromStr};
+
+use roxmltree::Node;
+use serde::{Deserialize, Serialize};
+
+use crate::config::{AuthMethod, ConnectConfig, HostKeyPolicy, ProxyConfig};
+use crate::error::{Error, Result};
+use crate::secret::Secret;
+use crate::ssh_config::{self, HostConfig};
+use crate::utils::config_dir;
+use crate::vault::Vault;
+
+/// 저장된 접속 설정 하나
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Profile {
+    /// 고유 이름 (CLI: minisftp @name)
+    pub name: String,
+    /// "Production/Web" ('/'로 중첩, None이면 최상위)
+    pub folder: Option<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+    pub host: String,
+    /// None이면 22
+    pub port: Option<u16>,
+    /// None이면 로컬 사용자명
+    pub username: Option<String>,
+    /// 인증 방식 (순서대로 시도, 비어 있으면 ssh-agent + 기본 키 파일)
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub auth: Vec<ProfileAuth>,
+    /// 점프 호스트 "[user@]host[:port],..." (-J / ProxyJump 표기)
+    pub jump: Option<String>,
+    /// 프록시 URL (socks5://..., http://...)
+    pub proxy: Option<String>,
+    /// ProxyCommand (proxy가 있으면 무시)
+    pub proxy_command: Option<String>,
+    pub host_key_policy: Option<HostKeyPolicy>,
+    /// 접속 후 시작 리모트 디렉토리
+    pub remote_dir: Option<String>,
+    /// 시작 로컬 디렉토리
+    pub local_dir: Option<PathBuf>,
+    pub comment: Option<String>,
+}
+
+/// 프로필 인증 방식 (비밀 값 없음)
+///
+/// 문자열 표기: "password", "publickey:<path>", "agent", "agent:<fingerprint|comment>", "keyboard-interactive"
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(into = "String", try_from = "String")]
+pub enum ProfileAuth {
+    /// 패스워드 (값은 연결 시 입력)
+    Password,
+    PublicKey(PathBuf),
+    Agent(Option<String>),
+    KeyboardInteractive,
+}
+
+impl fmt::Display for ProfileAuth {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ProfileAuth::Password             => write!(f, "password"),
+            ProfileAuth::PublicKey(path)      => write!(f, "publickey:{}", path.display()),
+            ProfileAuth::Agent(None)          => write!(f, "agent"),
+            ProfileAuth::Agent(Some(id))      => write!(f, "agent:{}", id),
+            ProfileAuth::KeyboardInteractive  => write!(f, "keyboard-interactive"),
+        }
+    }
+}
+
+impl FromStr for ProfileAuth {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let (kind, arg) = match s.split_once(':') {
+            Some((kind, arg)) => (kind, Some(arg)),
+            None => (s, None),
+        };
+        match (kind, arg) {
+            ("password", None)              => Ok(ProfileAuth::Password),
+            ("publickey", Some(path))       => Ok(ProfileAuth::PublicKey(PathBuf::from(path))),
+            ("agent", identity)             => Ok(ProfileAuth::Agent(identity.map(str::to_string))),
+            ("keyboard-interactive", None)  => Ok(ProfileAuth::KeyboardInteractive),
+            _ => Err(Error::Profile(format!("invalid auth method: {}", s))),
+        }
+    }
+}
+
+impl From<ProfileAuth> for String {
+    fn from(auth: ProfileAuth) -> Self {
+        auth.to_string()
+    }
+}
+
+impl TryFrom<String> for ProfileAuth {
+    type Error = Error;
+
+    fn try_from(s: String) -> Result<Self> {
+        s.parse()
+    }
+}
+
+impl Profile {
+    /// 패스워드 인증을 쓰는 프로필인지 (호출측에서 입력받아 auth_methods에 추가)
+    pub fn uses_password(&self) -> bool {
+        self.auth.contains(&ProfileAuth::Password)
+    }
+
+    /// 폴더 경로 + 이름 ("Production/Web/prod-web")
+    pub fn path(&self) -> String {
+        match &self.folder {
+            Some(folder) => format!("{}/{}", folder, self.name),
+            None => self.name.clone(),
+        }
+    }
+
+    /// 볼트 항목 id (패스워드: "name/password", 키 passphrase: "name/passphrase:<path>")
+    ///
+    /// agent / keyboard-interactive는 저장할 값이 없으므로 None
+    pub fn secret_id(&self, auth: &ProfileAuth) -> Option<String> {
+        match auth {
+            ProfileAuth::Password => Some(format!("{}/password", self.name)),
+            ProfileAuth::PublicKey(path) => Some(format!("{}/passphrase:{}", self.name, path.display())),
+            ProfileAuth::Agent(_) | ProfileAuth::KeyboardInteractive => None,
+        }
+    }
+
+    /// ConnectConfig로 변환 (패스워드는 포함하지 않음, HostConfig::to_connect_config와 같은 기본값)
+    ///
+    /// jump의 각 hop은 ~/.ssh/config 별칭으로 해석
+    pub fn to_connect_config(&self) -> Result<ConnectConfig> {
+        self.build_config(|_| Ok(None))
+    }
+
+    /// ConnectConfig로 변환 + 볼트에 저장된 패스워드 / passphrase 채움
+    ///
+    /// 볼트에 없는 패스워드는 빠짐 (호출측에서 입력), 잠겨 있으면 Error::VaultLocked
+    pub fn to_connect_config_with_vault(&self, vault: &mut Vault) -> Result<ConnectConfig> {
+        self.build_config(|id| vault.get(id))
+    }
+
+    fn build_config(&self, mut secret: impl FnMut(&str) -> Result<Option<Secret>>) -> Result<ConnectConfig> {
+        let host = HostConfig { user: self.username.clone(), ..HostConfig::default() };
+        let mut config = host.to_connect_config(&self.host)?;
+        if let Some(port) = self.port {
+            config.port = port;
+        }
+        if !self.auth.is_empty() {
+            config.auth_methods.clear();
+            for auth in &self.auth {
+                let stored = match self.secret_id(auth) {
+                    Some(id) => secret(&id)?,
+                    None => None,
+                };
+                config.auth_methods.extend(match auth {
+                    ProfileAuth::Password => stored.map(AuthMethod::Password),
+                    ProfileAuth::PublicKey(path) => Some(AuthMethod::PublicKey {
+                        private_key_path: PathBuf::from(ssh_config::expand_tilde(&path.to_string_lossy())),
+                        passphrase: stored,
+                    }),
+                    ProfileAuth::Agent(identity) => Some(AuthMethod::Agent { identity: identity.clone() }),
+                    ProfileAuth::KeyboardInteractive => Some(AuthMethod::KeyboardInteractive { submethods: None }),
+                });
+            }
+        }
+        if let Some(policy) = &self.host_key_policy {
+            config.host_key_policy = policy.clone();
+        }
+        if let Some(spec) = &self.jump {
+            let path = ssh_config::default_path().unwrap_or_default();
+            config.jump_hosts = ssh_config::parse_proxy_jump(&path, spec)?;
+        }
+        config.proxy = match (&self.proxy, &self.proxy_command) {
+            (Some(url), _) => Some(ProxyConfig::from_url(url).ok_or_else(|| {
+                Error::Profile(format!("{}: invalid proxy URL: {}", self.name, url))
+            })?),
+            (None, Some(command)) => Some(ProxyConfig::Command(command.clone())),
+            (None, None) => None,
+        };
+        Ok(config)
+    }
+}
+
+/// FileZilla sitemanager.xml 가져오기 결과
+#[derive(Debug, Clone, Default)]
+pub struct FileZillaImport {
+    /// 추가된 프로필 이름 (이름이 겹치면 "-2", "-3" 붙임)
+    pub imported: Vec<String>,
+    /// 건너뛴 사이트 ("이름 (이유)")
+    pub skipped: Vec<String>,
+}
+
+/// 프로필 저장소 (profiles.toml 하나)
+#[derive(Debug, Clone, Default)]
+pub struct ProfileStore {
+    path: PathBuf,
+    profiles: Vec<Profile>,
+}
+
+impl ProfileStore {
+    /// 기본 저장 위치 (config_dir()/profiles.toml)
+    pub fn default_path() -> Option<PathBuf> {
+        config_dir().map(|dir| dir.join("profiles.toml"))
+    }
+
+    /// 기본 위치의 저장소 열기
+    pub fn open_default() -> Result<Self> {
+        let path = Self::default_path()
+            .ok_or_else(|| Error::Profile("cannot locate config directory".to_string()))?;
+        Self::open(path)
+    }
+
+    /// 저장소 열기 (파일이 없으면 빈 저장소, save() 시 생성)
+    pub fn open(path: impl Into<PathBuf>) -> Result<Self> {
+        let path = path.into();
+        let profiles = if path.exists() {
+            let content = std::fs::read_to_string(&path)?;
+            from_toml(&content).map_err(|e| Error::Profile(format!("{}: {}", path.display(), e)))?
+        } else {
+            Vec::new()
+        };
+        Ok(Self { path, profiles })
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// 파일에 저장 (임시 파일에 쓴 뒤 교체)
+    pub fn save(&self) -> Result<()> {
+        if let Some(dir) = self.path.parent() {
+            std::fs::create_dir_all(dir)?;
+        }
+        let tmp = self.path.with_extension("toml.tmp");
+        std::fs::write(&tmp, to_toml(&self.profiles)?)?;
+        std::fs::rename(&tmp, &self.path)?;
+        Ok(())
+    }
+
+    /// 저장 순서대로
+    pub fn profiles(&self) -> &[Profile] {
+        &self.profiles
+    }
+
+    pub fn get(&self, name: &str) -> Option<&Profile> {
+        self.profiles.iter().find(|p| p.name == name)
+    }
+
+    /// 추가 또는 같은 이름 교체 (이름 / 호스트가 비어 있으면 에러)
+    pub fn upsert(&mut self, profile: Profile) -> Result<()> {
+        validate(&profile)?;
+        match self.profiles.iter_mut().find(|p| p.name == profile.name) {
+            Some(existing) => *existing = profile,
+            None => self.profiles.push(profile),
+        }
+        Ok(())
+    }
+
+    pub fn remove(&mut self, name: &str) -> Option<Profile> {
+        let idx = self.profiles.iter().position(|p| p.name == name)?;
+        Some(self.profiles.remove(idx))
+    }
+
+    /// 모든 폴더 경로 (상위 폴더 포함, 정렬)
+    pub fn folders(&self) -> Vec<String> {
+        let mut folders: Vec<String> = Vec::new();
+        for folder in self.profiles.iter().filter_map(|p| p.folder.as_deref()) {
+            let mut path = String::new();
+            for part in folder.split('/') {
+                if !path.is_empty() {
+                    path.push('/');
+                }
+                path.push_str(part);
+                if !folders.contains(&path) {
+                    folders.push(path.clone());
+                }
+            }
+        }
+        folders.sort();
+        folders
+    }
+
+    /// 폴더(하위 폴더 포함)에 속한 프로필
+    pub fn in_folder<'a>(&'a self, folder: &'a str) -> impl Iterator<Item = &'a Profile> + 'a {
+        let folder = folder.trim_matches('/');
+        self.profiles.iter().filter(move |p| {
+            p.folder.as_deref().is_some_and(|f| {
+                f == folder || f.strip_prefix(folder).is_some_and(|rest| rest.starts_with('/'))
+            })
+        })
+    }
+
+    /// 태그가 붙은 프로필 (대소문자 무시)
+    pub fn with_tag<'a>(&'a self, tag: &'a str) -> impl Iterator<Item = &'a Profile> + 'a {
+        self.profiles.iter().filter(move |p| p.tags.iter().any(|t| t.eq_ignore_ascii_case(tag)))
+    }
+
+    /// FileZilla sitemanager.xml 가져오기 (저장은 호출측에서 save())
+    ///
+    /// SFTP 사이트만 가져옴, 폴더 구조 유지, 저장된 패스워드는 가져오지 않음
+    pub fn import_filezilla(&mut self, path: &Path) -> Result<FileZillaImport> {
+        let content = std::fs::read_to_string(path)?;
+        let invalid = |m: String| Error::Profile(format!("{}: {}", path.display(), m));
+        let options = roxmltree::ParsingOptions { allow_dtd: true, ..Default::default() };
+        let doc = roxmltree::Document::parse_with_options(&content, options)
+            .map_err(|e| invalid(e.to_string()))?;
+        let servers = child(doc.root_element(), "Servers")
+            .ok_or_else(|| invalid("no <Servers> element (not a FileZilla site manager file?)".to_string()))?;
+
+        let mut report = FileZillaImport::default();
+        self.import_folder(servers, None, &mut report);
+        Ok(report)
+    }
+
+    fn import_folder(&mut self, node: Node, folder: Option<&str>, report: &mut FileZillaImport) {
+        for child in node.children().filter(Node::is_element) {
+            match child.tag_name().name() {
+                "Folder" => {
+                    let text = own_text(child);
+                    let name = text.trim();
+                    let path = match folder {
+                        Some(parent) => format!("{}/{}", parent, name),
+                        None => name.to_string(),
+                    };
+                    self.import_folder(child, Some(&path), report);
+                }
+                "Server" => match filezilla_profile(child, folder) {
+                    Ok(mut profile) => {
+                        profile.name = self.unique_name(&profile.name);
+                        report.imported.push(profile.name.clone());
+                        self.profiles.push(profile);
+                    }
+                    Err(skipped) => report.skipped.push(skipped),
+                },
+                _ => {}
+            }
+        }
+    }
+
+    /// 겹치지 않는 이름 (name, name-2, name-3, ...)
+    fn unique_name(&self, name: &str) -> String {
+        (1..)
+            .map(|n| if n == 1 { name.to_string() } else { format!("{}-{}", name, n) })
+            .find(|candidate| self.get(candidate).is_none())
+            .expect("unbounded range")
+    }
+}
+
+fn validate(profile: &Profile) -> Result<()> {
+    if profile.name.trim().is_empty() {
+        return Err(Error::Profile("profile name is empty".to_string()));
+    }
+    if profile.host.trim().is_empty() {
+        return Err(Error::Profile(format!("{}: host is empty", profile.name)));
+    }
+    Ok(())
+}
+
+// ── profiles.toml ───────────────────────────────────────────────────────────
+
+/// profiles.toml 최상위 ([[profile]] 테이블 배열)
+#[derive(Serialize, Deserialize)]
+struct ProfilesFile<'a> {
+    #[serde(default)]
+    profile: Cow<'a, [Profile]>,
+}
+
+/// profiles.toml → 프로필 목록 (폴더 앞뒤 '/' 정리, 이름 / 호스트 확인)
+fn from_toml(content: &str) -> std::result::Result<Vec<Profile>, String> {
+    let file: ProfilesFile = toml::from_str(content).map_err(|e| e.to_string())?;
+    let mut profiles = file.profile.into_owned();
+    for profile in &mut profiles {
+        if let Some(folder) = &mut profile.folder {
+            *folder = folder.trim_matches('/').to_string();
+        }
+        validate(profile).map_err(|e| e.to_string())?;
+    }
+    Ok(profiles)
+}
+
+/// 프로필 목록 → profiles.toml
+fn to_toml(profiles: &[Profile]) -> Result<String> {
+    let file = ProfilesFile { profile: Cow::Borrowed(profiles) };
+    let body = toml::to_string(&file).map_err(|e| Error::Profile(e.to_string()))?;
+    Ok(format!("# miniSFTP connection profiles\n\n{}", body))
+}
+
+// ── FileZilla sitemanager.xml ────────────────────────────────────────────────
+//
+// <FileZilla3>
+//   <Servers>
+//     <Server> <Host/> <Port/> <Protocol>1</Protocol> <User/> <Logontype/> <Keyfile/> <Name/> ... </Server>
+//     <Folder>폴더 이름 <Server>...</Server> <Folder>...</Folder> </Folder>
+//   </Servers>
+// </FileZilla3>
+//
+// Protocol: 0 FTP, 1 SFTP, 3 / 4 FTPS ...
+// Logontype: 0 anonymous, 1 normal, 2 ask, 3 interactive, 4 account, 5 key file
+
+/// <Server> → Profile (SFTP가 아니면 Err("이름 (이유)"))
+fn filezilla_profile(server: Node, folder: Option<&str>) -> std::result::Result<Profile, String> {
+    let text = |tag: &str| {
+        child(server, tag).map(|n| own_text(n).trim().to_string()).filter(|s| !s.is_empty())
+    };
+    let host = text("Host");
+    // FileZilla 3 초기 버전은 이름을 <Server>의 텍스트로 저장
+    let name = text("Name")
+        .or_else(|| Some(own_text(server).trim().to_string()).filter(|s| !s.is_empty()))
+        .or_else(|| host.clone())
+        .unwrap_or_else(|| "unnamed".to_string());
+
+    let protocol = text("Protocol").unwrap_or_else(|| "0".to_string());
+    if protocol != "1" {
+        return Err(format!("{} (not SFTP, protocol {})", name, protocol));
+    }
+    let host = host.ok_or_else(|| format!("{} (no host)", name))?;
+
+    let auth = match text("Logontype").as_deref() {
+        Some("3") => vec![ProfileAuth::KeyboardInteractive],
+        Some("5") => match text("Keyfile") {
+            Some(key) => vec![ProfileAuth::PublicKey(PathBuf::from(key))],
+            None => Vec::new(),
+        },
+        _ => vec![ProfileAuth::Password],
+    };
+
+    Ok(Profile {
+        name,
+        folder: folder.map(str::to_string),
+        host,
+        port: text("Port").and_then(|p| p.parse().ok()),
+        username: text("User"),
+        auth,
+        remote_dir: text("RemoteDir").and_then(|d| filezilla_remote_dir(&d)),
+        local_dir: text("LocalDir").map(PathBuf::from),
+        comment: text("Comments"),
+        ..Default::default()
+    })
+}
+
+/// FileZilla 리모트 경로 표기 → 경로 ("1 0 3 var 3 www" → "/var/www")
+///
+/// 서버 타입, prefix 길이 [prefix], (길이 이름)* — Unix(0, 1) 경로만 변환
+fn filezilla_remote_dir(encoded: &str) -> Option<String> {
+    let mut rest = encoded.chars().peekable();
+    let number = |rest: &mut Peekable<Chars>| -> Option<usize> {
+        skip_whitespace(rest);
+        let mut digits = String::new();
+        while let Some(c) = rest.next_if(char::is_ascii_digit) {
+            digits.push(c);
+        }
+        rest.next_if_eq(&' ');
+        digits.parse().ok()
+    };
+
+    let server_type = number(&mut rest)?;
+    let prefix_len = number(&mut rest)?;
+    if server_type > 1 || prefix_len > 0 {
+        return None;
+    }
+    let mut segments = Vec::new();
+    while rest.peek().is_some() {
+        let len = number(&mut rest)?;
+        segments.push(rest.by_ref().take(len).collect::<String>());
+        rest.next_if_eq(&' ');
+    }
+    Some(format!("/{}", segments.join("/")))
+}
+
+/// 이름이 tag인 첫 자식 요소
+fn child<'a, 'input>(node: Node<'a, 'input>, tag: &str) -> Option<Node<'a, 'input>> {
+    node.children().find(|c| c.has_tag_name(tag))
+}
+
+/// 직계 텍스트를 이어붙인 값 (자식 요소의 텍스트는 제외, CDATA / 엔티티는 roxmltree가 처리)
+fn own_text(node: Node) -> String {
+    node.children().filter(Node::is_text).filter_map(|c| c.text()).collect()
+}
+
+fn skip_whitespace(chars: &mut Peekable<Chars>) {
+    while chars.next_if(|c| c.is_whitespace()).is_some() {}
+}
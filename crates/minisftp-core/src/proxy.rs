@@ -0,0 +1,317 @@
+// miniSFTP Proxy Transport
+// author: kodeholic (powered by Claude)
+//
+// TCP 직접 연결 대신 SSH를 실어 나를 스트림을 만듦
+//
+// ProxyCommand : sh -c "<command>" 실행, stdin/stdout이 SSH 스트림 (%h %p %r %% 치환)
+// SOCKS5       : RFC 1928 CONNECT (도메인 주소 타입 → 이름 해석은 프록시가 수행)
+//                RFC 1929 username/password 인증
+// HTTP CONNECT : "CONNECT host:port HTTP/1.1" + Proxy-Authorization: Basic
+//
+// 결과 스트림은 client::connect_stream()에 그대로 전달
+
+use std::pin::Pin;
+use std::process::Stdio;
+use std::task::{Context, Poll};
+
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt, Join, ReadBuf};
+use tokio::net::TcpStream;
+use tokio::process::{Child, ChildStdin, ChildStdout, Command};
+
+use crate::config::{ProxyConfig, ProxyCredentials};
+use crate::error::{Error, ProxyErrorKind, Result};
+use crate::secret::Zeroizing;
+
+/// SSH 전송 스트림 (TcpStream / ProxyCommand 파이프)
+pub(crate) trait ProxyStream: AsyncRead + AsyncWrite + Unpin + Send {}
+impl<T: AsyncRead + AsyncWrite + Unpin + Send> ProxyStream for T {}
+
+/// 프록시를 거쳐 host:port로 가는 스트림 생성
+pub(crate) async fn connect(proxy: &ProxyConfig, host: &str, port: u16, username: &str) -> Result<Box<dyn ProxyStream>> {
+    match proxy {
+        ProxyConfig::Command(command) => {
+            let command = expand_command(command, host, port, username);
+            tracing::info!("[proxy] executing proxy command: {}", command);
+            Ok(Box::new(CommandStream::spawn(&command)?))
+        }
+        ProxyConfig::Socks5 { host: proxy_host, port: proxy_port, credentials } => {
+            tracing::info!("[proxy] connecting to {}:{} via SOCKS5 {}:{}", host, port, proxy_host, proxy_port);
+            let proxy = format!("SOCKS5 proxy {}:{}", proxy_host, proxy_port);
+            let mut tcp = TcpStream::connect((proxy_host.as_str(), *proxy_port)).await
+                .map_err(|e| Error::proxy_io(&proxy, e))?;
+            socks5_handshake(&mut tcp, host, port, credentials.as_ref()).await
+                .map_err(|e| with_proxy_context(&proxy, e))?;
+            Ok(Box::new(tcp))
+        }
+        ProxyConfig::HttpConnect { host: proxy_host, port: proxy_port, credentials } => {
+            tracing::info!("[proxy] connecting to {}:{} via HTTP CONNECT {}:{}", host, port, proxy_host, proxy_port);
+            let proxy = format!("HTTP proxy {}:{}", proxy_host, proxy_port);
+            let mut tcp = TcpStream::connect((proxy_host.as_str(), *proxy_port)).await
+                .map_err(|e| Error::proxy_io(&proxy, e))?;
+            http_connect_handshake(&mut tcp, host, port, credentials.as_ref()).await
+                .map_err(|e| with_proxy_context(&proxy, e))?;
+            Ok(Box::new(tcp))
+        }
+    }
+}
+
+/// 핸드셰이크 중 `?`로 올라온 io 에러 → 프록시 통신 실패 (재시도 대상)
+fn with_proxy_context(proxy: &str, e: Error) -> Error {
+    match e {
+        Error::Io(e) => Error::proxy_io(proxy, e),
+        e => e,
+    }
+}
+
+/// OpenSSH ProxyCommand 토큰 치환 (%h host, %p port, %r user, %% → %)
+fn expand_command(command: &str, host: &str, port: u16, username: &str) -> String {
+    let mut out = String::with_capacity(command.len());
+    let mut chars = command.chars();
+    while let Some(ch) = chars.next() {
+        if ch != '%' {
+            out.push(ch);
+            continue;
+        }
+        match chars.next() {
+            Some('h') => out.push_str(host),
+            Some('p') => out.push_str(&port.to_string()),
+            Some('r') => out.push_str(username),
+            Some('%') => out.push('%'),
+            Some(other) => { out.push('%'); out.push(other); }
+            None => out.push('%'),
+        }
+    }
+    out
+}
+
+// ── ProxyCommand ──────────────────────────────────────────────────────────────
+
+/// 자식 프로세스의 stdout(읽기) + stdin(쓰기)을 하나의 스트림으로
+///
+/// child는 스트림과 수명을 같이함 (drop 시 kill_on_drop으로 종료)
+struct CommandStream {
+    _child: Child,
+    io: Join<ChildStdout, ChildStdin>,
+}
+
+impl CommandStream {
+    fn spawn(command: &str) -> Result<Self> {
+        #[cfg(unix)]
+        let mut cmd = {
+            let mut cmd = Command::new("sh");
+            cmd.arg("-c").arg(command);
+            cmd
+        };
+        #[cfg(windows)]
+        let mut cmd = {
+            let mut cmd = Command::new("cmd");
+            cmd.arg("/C").arg(command);
+            cmd
+        };
+
+        let mut child = cmd
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::inherit())
+            .kill_on_drop(true)
+            .spawn()
+            .map_err(|e| Error::Proxy {
+                kind: ProxyErrorKind::Command,
+                message: format!("cannot execute proxy command '{}': {}", command, e),
+                source: Some(e),
+            })?;
+
+        let stdin  = child.stdin.take().ok_or_else(|| Error::proxy(ProxyErrorKind::Command, "proxy command has no stdin"))?;
+        let stdout = child.stdout.take().ok_or_else(|| Error::proxy(ProxyErrorKind::Command, "proxy command has no stdout"))?;
+        Ok(Self { _child: child, io: tokio::io::join(stdout, stdin) })
+    }
+}
+
+impl AsyncRead for CommandStream {
+    fn poll_read(mut self: Pin<&mut Self>, cx: &mut Context<'_>, buf: &mut ReadBuf<'_>) -> Poll<std::io::Result<()>> {
+        Pin::new(&mut self.io).poll_read(cx, buf)
+    }
+}
+
+impl AsyncWrite for CommandStream {
+    fn poll_write(mut self: Pin<&mut Self>, cx: &mut Context<'_>, buf: &[u8]) -> Poll<std::io::Result<usize>> {
+        Pin::new(&mut self.io).poll_write(cx, buf)
+    }
+    fn poll_flush(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<std::io::Result<()>> {
+        Pin::new(&mut self.io).poll_flush(cx)
+    }
+    fn poll_shutdown(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<std::io::Result<()>> {
+        Pin::new(&mut self.io).poll_shutdown(cx)
+    }
+}
+
+// ── SOCKS5 ────────────────────────────────────────────────────────────────────
+
+const SOCKS_VERSION: u8 = 0x05;
+const SOCKS_AUTH_NONE: u8 = 0x00;
+const SOCKS_AUTH_PASSWORD: u8 = 0x02;
+const SOCKS_AUTH_UNACCEPTABLE: u8 = 0xFF;
+const SOCKS_CMD_CONNECT: u8 = 0x01;
+const SOCKS_ATYP_IPV4: u8 = 0x01;
+const SOCKS_ATYP_DOMAIN: u8 = 0x03;
+const SOCKS_ATYP_IPV6: u8 = 0x04;
+
+async fn socks5_handshake(
+    tcp: &mut TcpStream,
+    host: &str,
+    port: u16,
+    credentials: Option<&ProxyCredentials>,
+) -> Result<()> {
+    // 1. 인증 방식 협상 (자격증명이 있으면 username/password도 제안)
+    let greeting: &[u8] = match credentials {
+        Some(_) => &[SOCKS_VERSION, 2, SOCKS_AUTH_NONE, SOCKS_AUTH_PASSWORD],
+        None    => &[SOCKS_VERSION, 1, SOCKS_AUTH_NONE],
+    };
+    tcp.write_all(greeting).await?;
+
+    let mut reply = [0u8; 2];
+    tcp.read_exact(&mut reply).await?;
+    if reply[0] != SOCKS_VERSION {
+        return Err(Error::proxy(ProxyErrorKind::Protocol, format!("not a SOCKS5 proxy (version {})", reply[0])));
+    }
+
+    match (reply[1], credentials) {
+        (SOCKS_AUTH_NONE, _) => {}
+        (SOCKS_AUTH_PASSWORD, Some(creds)) => {
+            // RFC 1929: ver(1) ulen(1) user plen(1) pass → ver(1) status(1)
+            let (user, pass) = (creds.username.as_bytes(), creds.password.expose().as_bytes());
+            if user.len() > 255 || pass.len() > 255 {
+                return Err(Error::proxy(ProxyErrorKind::Protocol, "SOCKS5 username/password too long"));
+            }
+            // 패스워드가 들어가는 버퍼는 전송 후 소거 (재할당으로 복사본이 남지 않도록 크기를 미리 잡음)
+            let mut req = Zeroizing::new(Vec::with_capacity(3 + user.len() + pass.len()));
+            req.extend_from_slice(&[0x01, user.len() as u8]);
+            req.extend_from_slice(user);
+            req.push(pass.len() as u8);
+            req.extend_from_slice(pass);
+            tcp.write_all(&req).await?;
+
+            let mut status = [0u8; 2];
+            tcp.read_exact(&mut status).await?;
+            if status[1] != 0x00 {
+                return Err(Error::proxy(ProxyErrorKind::Auth, "SOCKS5 proxy rejected username/password"));
+            }
+        }
+        (SOCKS_AUTH_PASSWORD, None) => {
+            return Err(Error::proxy(ProxyErrorKind::Auth, "SOCKS5 proxy requires username/password"));
+        }
+        (SOCKS_AUTH_UNACCEPTABLE, _) => {
+            return Err(Error::proxy(ProxyErrorKind::Auth, "SOCKS5 proxy accepted none of the offered auth methods"));
+        }
+        (method, _) => {
+            return Err(Error::proxy(ProxyErrorKind::Protocol, format!("SOCKS5 proxy selected unsupported auth method {:#04x}", method)));
+        }
+    }
+
+    // 2. CONNECT (도메인 이름 그대로 전달)
+    if host.len() > 255 {
+        return Err(Error::proxy(ProxyErrorKind::Protocol, "host name too long for SOCKS5"));
+    }
+    let mut req = vec![SOCKS_VERSION, SOCKS_CMD_CONNECT, 0x00, SOCKS_ATYP_DOMAIN, host.len() as u8];
+    req.extend_from_slice(host.as_bytes());
+    req.extend_from_slice(&port.to_be_bytes());
+    tcp.write_all(&req).await?;
+
+    // ver(1) rep(1) rsv(1) atyp(1) bnd.addr bnd.port(2)
+    let mut head = [0u8; 4];
+    tcp.read_exact(&mut head).await?;
+    if head[1] != 0x00 {
+        return Err(Error::proxy(
+            ProxyErrorKind::Connect,
+            format!("SOCKS5 CONNECT to {}:{} failed: {}", host, port, socks5_reply_str(head[1])),
+        ));
+    }
+    let addr_len = match head[3] {
+        SOCKS_ATYP_IPV4   => 4,
+        SOCKS_ATYP_IPV6   => 16,
+        SOCKS_ATYP_DOMAIN => {
+            let mut len = [0u8; 1];
+            tcp.read_exact(&mut len).await?;
+            len[0] as usize
+        }
+        other => return Err(Error::proxy(ProxyErrorKind::Protocol, format!("SOCKS5 reply has unknown address type {}", other))),
+    };
+    let mut bound = vec![0u8; addr_len + 2];
+    tcp.read_exact(&mut bound).await?;
+
+    Ok(())
+}
+
+fn socks5_reply_str(code: u8) -> &'static str {
+    match code {
+        0x01 => "general failure",
+        0x02 => "connection not allowed by ruleset",
+        0x03 => "network unreachable",
+        0x04 => "host unreachable",
+        0x05 => "connection refused",
+        0x06 => "TTL expired",
+        0x07 => "command not supported",
+        0x08 => "address type not supported",
+        _    => "unknown error",
+    }
+}
+
+// ── HTTP CONNECT ──────────────────────────────────────────────────────────────
+
+/// 응답 헤더 최대 크기 (비정상 프록시 방어)
+const HTTP_MAX_HEADER: usize = 16 * 1024;
+
+async fn http_connect_handshake(
+    tcp: &mut TcpStream,
+    host: &str,
+    port: u16,
+    credentials: Option<&ProxyCredentials>,
+) -> Result<()> {
+    // IPv6 리터럴은 [addr]:port
+    let authority = if host.contains(':') { format!("[{}]:{}", host, port) } else { format!("{}:{}", host, port) };
+
+    // 자격증명이 들어가는 버퍼는 전송 후 소거
+    let mut req = Zeroizing::new(format!("CONNECT {0} HTTP/1.1\r\nHost: {0}\r\n", authority));
+    if let Some(creds) = credentials {
+        let userinfo = Zeroizing::new(format!("{}:{}", creds.username, creds.password.expose()));
+        let token = Zeroizing::new(data_encoding::BASE64.encode(userinfo.as_bytes()));
+        req.push_str("Proxy-Authorization: Basic ");
+        req.push_str(&token);
+        req.push_str("\r\n");
+    }
+    req.push_str("\r\n");
+    tcp.write_all(req.as_bytes()).await?;
+
+    // 헤더 끝(\r\n\r\n)까지 1바이트씩 읽음 → 뒤따르는 SSH 배너를 소비하지 않기 위함
+    let mut head = Vec::new();
+    let mut byte = [0u8; 1];
+    while !head.ends_with(b"\r\n\r\n") {
+        if head.len() >= HTTP_MAX_HEADER {
+            return Err(Error::proxy(ProxyErrorKind::Protocol, "HTTP proxy response header too large"));
+        }
+        if tcp.read(&mut byte).await? == 0 {
+            return Err(Error::Io(std::io::Error::new(
+                std::io::ErrorKind::UnexpectedEof, "HTTP proxy closed the connection",
+            )));
+        }
+        head.push(byte[0]);
+    }
+
+    let head = String::from_utf8_lossy(&head);
+    let status_line = head.lines().next().unwrap_or_default();
+    // "HTTP/1.1 200 Connection established"
+    let mut parts = status_line.splitn(3, ' ');
+    let version = parts.next().unwrap_or_default();
+    let code    = parts.next().unwrap_or_default();
+    if !version.starts_with("HTTP/") {
+        return Err(Error::proxy(ProxyErrorKind::Protocol, format!("invalid HTTP proxy response: {}", status_line)));
+    }
+    match code {
+        "200" => Ok(()),
+        "407" => Err(Error::proxy(ProxyErrorKind::Auth, match credentials {
+            Some(_) => "HTTP proxy rejected credentials (407)",
+            None    => "HTTP proxy requires authentication (407)",
+        })),
+        _ => Err(Error::proxy(ProxyErrorKind::Connect, format!("HTTP CONNECT to {} failed: {}", authority, status_line))),
+    }
+}
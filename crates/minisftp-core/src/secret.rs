@@ -0,0 +1,66 @@
+// miniSFTP Secret (패스워드 / passphrase 보호)
+// author: kodeholic (powered by Claude)
+//
+// Secret : Debug / Display에서 "***"로 가림, drop 시 메모리 0으로 소거 (zeroize)
+//          값은 expose()로만 꺼냄 → 로그/패닉 메시지에 평문이 찍히지 않음
+//
+// russh는 RUST_LOG=debug / trace에서 암호화 전 패킷을 그대로 출력함
+// (USERAUTH_REQUEST의 패스워드, keyboard-interactive 응답 포함)
+//   → 구독자(subscriber) 필터에서 is_sensitive_log()인 이벤트를 버림
+//     (EnvFilter 지시어는 더 구체적인 RUST_LOG=russh::session=trace에 덮어써지므로 사용하지 않음)
+
+use std::fmt;
+
+// 입력 버퍼 소거용 re-export (CLI 패스워드 입력 등)
+pub use zeroize::Zeroizing;
+
+/// 평문 패킷이 찍힐 수 있는 로그인지 (russh의 debug, trace)
+///
+/// tracing_subscriber::filter::filter_fn(|m| !is_sensitive_log(m.target(), m.level()))
+pub fn is_sensitive_log(target: &str, level: &tracing::Level) -> bool {
+    let russh = target == "russh" || target.starts_with("russh::");
+    russh && *level > tracing::Level::INFO
+}
+
+/// 민감한 문자열 (패스워드, passphrase, 프록시 패스워드)
+#[derive(Clone, Default)]
+pub struct Secret(Zeroizing<String>);
+
+impl Secret {
+    pub fn new(value: impl Into<String>) -> Self {
+        Self(Zeroizing::new(value.into()))
+    }
+
+    /// 평문 값 (인증 요청에 전달할 때만 사용)
+    pub fn expose(&self) -> &str {
+        &self.0
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+impl fmt::Debug for Secret {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Secret(***)")
+    }
+}
+
+impl fmt::Display for Secret {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "***")
+    }
+}
+
+impl From<String> for Secret {
+    fn from(value: String) -> Self {
+        Self::new(value)
+    }
+}
+
+impl From<&str> for Secret {
+    fn from(value: &str) -> Self {
+        Self::new(value)
+    }
+}
@@ -3,121 +3,807 @@
 //
 // russh로 SSH 연결/인증을 처리하고
 // russh-sftp로 SFTP 세션을 수립합니다.
+//
+// 연결 감시: ClientHandler::disconnected / channel_close가 백그라운드에서 호출되면
+// 공유 상태(Link)를 통해 즉시 상태 전이 + observer 통지
+//
+// 다중 채널: SSH 연결 하나에 SFTP 채널 여러 개 (connect()가 주 채널, open_channel()로 추가)
+//   주 채널이 서버에서 닫히면 연결 끊김, 추가 채널이 닫히면 해당 SftpClient만 사용 불가
+//   disconnect() / reconnect() 시 열린 채널을 모두 닫은 뒤 연결 종료
 
-use std::sync::Arc;
-use russh::client;
+use std::future::Future;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex, Weak};
+use std::time::Duration;
+use russh::{client, ChannelId, Disconnect};
+use tokio::net::TcpStream;
+use russh_sftp::client::RawSftpSession;
 use russh_sftp::client::SftpSession as RusshSftpSession;
 
-use crate::config::{ConnectConfig, AuthMethod};
+use crate::auth::{authenticate, KeyboardInteractivePrompter};
+use crate::config::{ConnectConfig, HostKeyPolicy, JumpHost};
 use crate::error::{Error, Result};
+use crate::known_hosts::{self, HostKeyInfo, HostKeyPrompter, HostKeyStatus};
+use crate::algorithms::{self, NegotiatedAlgorithms};
+use crate::handshake::{self, OnServerId, ServerHello, SftpHello};
+use crate::proxy::{self, ProxyStream};
 use crate::state::{ConnectionState, ConnectionObserver};
-use crate::sftp::SftpClient;
+use crate::sftp::{RawChannel, SftpClient};
 
 // russh 클라이언트 핸들러 (서버 이벤트 처리)
-struct ClientHandler;
+//
+// Error 타입을 crate Error로 지정 → check_server_key의 Error::HostKey가
+// client::connect()의 반환값으로 그대로 전달됨
+struct ClientHandler {
+    host: String,
+    port: u16,
+    policy: HostKeyPolicy,
+    known_hosts: Option<PathBuf>,
+    prompter: Option<Arc<dyn HostKeyPrompter>>,
+    link: Arc<Link>,
+    // 끊김 감지 대상 여부: 대상 서버 연결만 true (점프 호스트가 끊기면 대상 연결도 끊기므로 거기서 감지)
+    monitor: bool,
+    // 핸드셰이크 / 배너 정보 기록용 (handshake()에서 설정)
+    hello: Option<Arc<Mutex<ServerHello>>>,
+}
 
-#[async_trait::async_trait]
 impl client::Handler for ClientHandler {
-    type Error = russh::Error;
+    type Error = Error;
 
     async fn check_server_key(
         &mut self,
-        _server_public_key: &russh::keys::key::PublicKey,
+        server_public_key: &russh::keys::PublicKey,
     ) -> std::result::Result<bool, Self::Error> {
-        // 학습용: 호스트키 검증 생략 (실서비스에선 known_hosts 확인 필요)
-        Ok(true)
+        if let Some(hello) = &self.hello {
+            let mut hello = hello.lock().unwrap();
+            hello.host_key_type = Some(known_hosts::key_type_name(server_public_key));
+            hello.fingerprint = Some(known_hosts::fingerprint(server_public_key));
+        }
+        self.verify_host_key(server_public_key).map(|_| true)
+    }
+
+    /// 인증 중 서버 배너 (법적 고지 등) → observer 전달 + ServerInfo에 기록
+    async fn auth_banner(
+        &mut self,
+        banner: &str,
+        _session: &mut client::Session,
+    ) -> std::result::Result<(), Self::Error> {
+        let addr = format!("{}:{}", self.host, self.port);
+        self.link.observer.on_banner(&addr, banner);
+        if let Some(hello) = &self.hello {
+            hello.lock().unwrap().banner.get_or_insert_with(String::new).push_str(banner);
+        }
+        Ok(())
+    }
+
+    /// 주 SFTP 채널이 서버에서 닫힘 (서브시스템 종료 등) → 연결 끊김으로 처리
+    async fn channel_close(
+        &mut self,
+        channel: ChannelId,
+        _session: &mut client::Session,
+    ) -> std::result::Result<(), Self::Error> {
+        if !self.monitor {
+            return Ok(());
+        }
+        if self.link.is_sftp_channel(channel) {
+            self.link.connection_lost("SFTP channel closed by server".to_string());
+        } else {
+            tracing::debug!("[session] channel {} closed", channel);
+        }
+        Ok(())
+    }
+
+    /// 전송 종료 (서버 disconnect 메시지, EOF, IO 에러)
+    async fn disconnected(
+        &mut self,
+        reason: client::DisconnectReason<Self::Error>,
+    ) -> std::result::Result<(), Self::Error> {
+        if !self.monitor {
+            return match reason {
+                client::DisconnectReason::ReceivedDisconnect(_) => Ok(()),
+                client::DisconnectReason::Error(e) => Err(e),
+            };
+        }
+        match reason {
+            client::DisconnectReason::ReceivedDisconnect(info) => {
+                self.link.remote_disconnect(&info);
+                Ok(())
+            }
+            client::DisconnectReason::Error(e) => {
+                self.link.connection_lost(format!("connection lost: {}", e));
+                Err(e)
+            }
+        }
     }
 }
 
-pub struct SftpSession {
-    state: ConnectionState,
+impl ClientHandler {
+    fn verify_host_key(&self, key: &russh::keys::PublicKey) -> Result<()> {
+        let fingerprint = known_hosts::fingerprint(key);
+        let reject = |message: String| Error::HostKey {
+            host: known_hosts::host_entry_name(&self.host, self.port),
+            fingerprint: fingerprint.clone(),
+            message,
+        };
+
+        if self.policy == HostKeyPolicy::AcceptAny {
+            tracing::warn!("[session] host key check disabled, accepting {}", fingerprint);
+            return Ok(());
+        }
+
+        let path = self.known_hosts.clone()
+            .or_else(known_hosts::default_path)
+            .ok_or_else(|| reject("cannot locate known_hosts (no home directory)".to_string()))?;
+
+        let status = known_hosts::check(&path, &self.host, self.port, key)?;
+        tracing::debug!("[session] host key {} → {:?}", fingerprint, status);
+
+        let accepted = match &status {
+            HostKeyStatus::Known { .. } => return Ok(()),
+            HostKeyStatus::Revoked { line } => {
+                return Err(reject(format!("key is revoked ({}:{})", path.display(), line)));
+            }
+            HostKeyStatus::Changed { line } => {
+                return Err(reject(format!(
+                    "REMOTE HOST IDENTIFICATION HAS CHANGED, offending key in {}:{}",
+                    path.display(), line,
+                )));
+            }
+            HostKeyStatus::CertAuthority { line } => {
+                return Err(reject(format!(
+                    "host is trusted only through @cert-authority ({}:{}), host certificates are not supported",
+                    path.display(), line,
+                )));
+            }
+            HostKeyStatus::Unknown => match self.policy {
+                HostKeyPolicy::AcceptNew => true,
+                HostKeyPolicy::Ask => {
+                    let prompter = self.prompter.as_ref()
+                        .ok_or_else(|| reject("host is unknown and no prompter is set".to_string()))?;
+                    prompter.confirm(&HostKeyInfo {
+                        host: self.host.clone(),
+                        port: self.port,
+                        key_type: known_hosts::key_type_name(key),
+                        fingerprint: fingerprint.clone(),
+                        status: status.clone(),
+                    })
+                }
+                _ => false,
+            },
+        };
+
+        if !accepted {
+            return Err(reject(format!("host is not in {}", path.display())));
+        }
+
+        // 신뢰한 키는 known_hosts에 추가 (실패해도 접속은 진행, OpenSSH 동작)
+        match known_hosts::learn(&path, &self.host, self.port, key) {
+            Ok(())  => tracing::info!("[session] added {} to {}", known_hosts::host_entry_name(&self.host, self.port), path.display()),
+            Err(e) => tracing::warn!("[session] failed to update {}: {}", path.display(), e),
+        }
+        Ok(())
+    }
+}
+
+/// SftpSession ↔ 백그라운드(ClientHandler) 공유 상태
+struct Link {
+    state: Mutex<ConnectionState>,
     observer: Box<dyn ConnectionObserver>,
+    sftp_channel: Mutex<Option<ChannelId>>,
 }
 
-impl SftpSession {
-    pub fn new(observer: Box<dyn ConnectionObserver>) -> Self {
-        Self { state: ConnectionState::Idle, observer }
+impl Link {
+    fn state(&self) -> ConnectionState {
+        self.state.lock().unwrap().clone()
     }
 
-    pub fn state(&self) -> &ConnectionState { &self.state }
-
-    fn transition(&mut self, next: ConnectionState) -> Result<()> {
-        if !self.state.can_transition_to(&next) {
-            return Err(Error::InvalidTransition { from: self.state.clone(), to: next });
+    fn transition(&self, next: ConnectionState) -> Result<()> {
+        let mut state = self.state.lock().unwrap();
+        if !state.can_transition_to(&next) {
+            return Err(Error::InvalidTransition { from: Box::new(state.clone()), to: Box::new(next) });
         }
-        let prev = std::mem::replace(&mut self.state, next);
-        self.observer.on_state_changed(&prev, &self.state);
+        let prev = std::mem::replace(&mut *state, next);
+        self.observer.on_state_changed(&prev, &state);
         Ok(())
     }
 
-    fn transition_to_error(&mut self, message: String) {
-        let current = self.state.clone();
+    fn transition_to_error(&self, message: String) {
+        let mut state = self.state.lock().unwrap();
+        let current = state.clone();
         let prev = std::mem::replace(
-            &mut self.state,
+            &mut *state,
             ConnectionState::Error { state: Box::new(current), message }
         );
-        self.observer.on_state_changed(&prev, &self.state);
+        self.observer.on_state_changed(&prev, &state);
+    }
+
+    fn is_sftp_channel(&self, channel: ChannelId) -> bool {
+        *self.sftp_channel.lock().unwrap() == Some(channel)
+    }
+
+    /// 사용 중(SftpReady)인 연결만 대상
+    /// 연결 수립 중 끊김은 connect()가 에러로 반환, disconnect() 중이면 의도된 종료
+    fn is_ready(&self) -> bool {
+        matches!(*self.state.lock().unwrap(), ConnectionState::SftpReady)
+    }
+
+    /// 서버가 정상 종료 (SSH_MSG_DISCONNECT) → Disconnecting → Disconnected
+    fn remote_disconnect(&self, info: &client::RemoteDisconnectInfo) {
+        if !self.is_ready() {
+            return;
+        }
+        tracing::info!("[session] server disconnected: {:?} {}", info.reason_code, info.message);
+        let _ = self.transition(ConnectionState::Disconnecting);
+        let _ = self.transition(ConnectionState::Disconnected);
+    }
+
+    /// 예기치 않은 끊김 (EOF, IO 에러, SFTP 채널 종료) → Error
+    fn connection_lost(&self, message: String) {
+        if !self.is_ready() {
+            return;
+        }
+        tracing::warn!("[session] {}", message);
+        self.transition_to_error(message);
+    }
+}
+
+pub struct SftpSession {
+    link: Arc<Link>,
+    // 대상 서버 연결 (disconnect()에서 종료, open_channel()에서 재사용)
+    ssh: Option<client::Handle<ClientHandler>>,
+    // 이 연결에서 연 SFTP 채널 (주 채널 포함, SftpClient가 모두 drop되면 자동으로 빠짐)
+    channels: Vec<Weak<RusshSftpSession>>,
+    // 확장 요청 / longname 조회용 채널 (posix-rename, chown 이름 조회, 이 연결의 SftpClient가 공유)
+    raw: Option<Arc<RawChannel>>,
+    // 마지막 connect() 설정 (reconnect()에서 재사용)
+    config: Option<ConnectConfig>,
+    prompter: Option<Box<dyn KeyboardInteractivePrompter>>,
+    host_key_prompter: Option<Arc<dyn HostKeyPrompter>>,
+    // 점프 호스트 연결 (대상 서버 연결이 이 위로 터널링되므로 세션 동안 유지)
+    jumps: Vec<client::Handle<ClientHandler>>,
+    // 마지막으로 협상된 대상 서버 알고리즘
+    negotiated: Option<NegotiatedAlgorithms>,
+    // 마지막으로 접속한 대상 서버 정보
+    server_info: Option<ServerInfo>,
+}
+
+/// 접속한 서버 정보 (SftpSession::server_info)
+#[derive(Debug, Clone)]
+pub struct ServerInfo {
+    /// SSH 식별 문자열 ("SSH-2.0-OpenSSH_9.6")
+    pub ssh_version: String,
+    /// 인증 중 받은 배너 (SSH_MSG_USERAUTH_BANNER, 없으면 None)
+    pub banner: Option<String>,
+    pub host_key_type: String,
+    /// "SHA256:..."
+    pub fingerprint: String,
+    pub algorithms: Option<NegotiatedAlgorithms>,
+    /// SFTP 프로토콜 버전 (SSH_FXP_VERSION, OpenSSH는 3)
+    pub sftp_version: u32,
+    /// SFTP 확장 (이름, 데이터) 서버가 보낸 순서대로
+    /// 예: ("posix-rename@openssh.com", "1"), ("statvfs@openssh.com", "2"), ("limits@openssh.com", "1")
+    pub extensions: Vec<(String, String)>,
+}
+
+impl ServerInfo {
+    /// 확장 지원 여부 ("posix-rename@openssh.com", "hardlink@openssh.com", "fsync@openssh.com" 등)
+    pub fn has_extension(&self, name: &str) -> bool {
+        self.extensions.iter().any(|(n, _)| n == name)
+    }
+}
+
+impl SftpSession {
+    pub fn new(observer: Box<dyn ConnectionObserver>) -> Self {
+        Self {
+            link: Arc::new(Link {
+                state: Mutex::new(ConnectionState::Idle),
+                observer,
+                sftp_channel: Mutex::new(None),
+            }),
+            ssh: None,
+            channels: Vec::new(),
+            raw: None,
+            config: None,
+            prompter: None,
+            host_key_prompter: None,
+            jumps: Vec::new(),
+            negotiated: None,
+            server_info: None,
+        }
+    }
+
+    /// 현재 상태 (백그라운드 감시로 바뀔 수 있으므로 복사본 반환)
+    pub fn state(&self) -> ConnectionState { self.link.state() }
+
+    /// SFTP 사용 가능 여부 (SftpReady 상태)
+    pub fn is_connected(&self) -> bool { self.link.is_ready() }
+
+    /// 대상 서버와 협상된 알고리즘 (마지막 connect 기준, 핸드셰이크 전이면 None)
+    pub fn negotiated_algorithms(&self) -> Option<&NegotiatedAlgorithms> { self.negotiated.as_ref() }
+
+    /// 대상 서버 정보: SSH 버전, 배너, 호스트키, SFTP 버전 / 확장 (마지막 connect 기준, 접속 전이면 None)
+    pub fn server_info(&self) -> Option<&ServerInfo> { self.server_info.as_ref() }
+
+    /// 열려 있는 SFTP 채널 수 (주 채널 포함, SftpClient가 살아 있는 것만, posix-rename 확장 채널은 제외)
+    pub fn channel_count(&self) -> usize {
+        self.channels.iter().filter(|c| c.strong_count() > 0).count()
+    }
+
+    /// keyboard-interactive 질문에 응답할 prompter 등록
+    pub fn set_prompter(&mut self, prompter: Box<dyn KeyboardInteractivePrompter>) {
+        self.prompter = Some(prompter);
+    }
+
+    /// 처음 보는 호스트키 확인용 prompter 등록 (HostKeyPolicy::Ask)
+    pub fn set_host_key_prompter(&mut self, prompter: Arc<dyn HostKeyPrompter>) {
+        self.host_key_prompter = Some(prompter);
+    }
+
+    fn transition(&self, next: ConnectionState) -> Result<()> {
+        self.link.transition(next)
+    }
+
+    fn transition_to_error(&self, message: String) {
+        self.link.transition_to_error(message)
+    }
+
+    fn handler(&self, host: &str, port: u16, config: &ConnectConfig) -> ClientHandler {
+        ClientHandler {
+            host: host.to_string(),
+            port,
+            policy: config.host_key_policy.clone(),
+            known_hosts: config.known_hosts_path.clone(),
+            prompter: self.host_key_prompter.clone(),
+            link: self.link.clone(),
+            monitor: false,
+            hello: None,
+        }
     }
 
     pub async fn connect(&mut self, config: &ConnectConfig) -> Result<SftpClient> {
-        // ---- TCP 연결 ----
         self.transition(ConnectionState::TcpConnecting)?;
+        self.config = Some(config.clone());
+        // 이전 연결에서 남은 핸들 (끊긴 연결의 점프 호스트 등) 정리
+        self.close_handles().await;
 
-        let russh_config = Arc::new(client::Config::default());
-        let handler = ClientHandler;
+        match self.establish(config).await {
+            Ok(sftp) => Ok(sftp),
+            Err(e) => {
+                self.transition_to_error(e.to_string());
+                // 대상 서버 단계에서 실패해도 이미 연결된 점프 호스트는 닫음
+                self.close_handles().await;
+                Err(e)
+            }
+        }
+    }
 
-        let addr = config.addr();
-        tracing::info!("[session] connecting to {}", addr);
+    /// TCP → 핸드셰이크 → 인증 → SFTP, 단계마다 config.timeouts 적용
+    async fn establish(&mut self, config: &ConnectConfig) -> Result<SftpClient> {
+        let timeouts = &config.timeouts;
+        let russh_config = Arc::new(russh_config(config)?);
+        let handler = ClientHandler {
+            monitor: true,
+            ..self.handler(&config.host, config.port, config)
+        };
 
-        let mut ssh = client::connect(russh_config, addr, handler)
-            .await
-            .map_err(|e| {
-                self.transition_to_error(e.to_string());
-                Error::Protocol(e.to_string())
-            })?;
+        // ---- TCP 연결 (프록시 / 점프 호스트 경유) ----
+        let stream = if config.jump_hosts.is_empty() {
+            tracing::info!("[session] connecting to {}", config.addr());
+            self.open_transport(config, &config.host, config.port, &config.username).await?
+        } else {
+            self.connect_via_jumps(config, russh_config.clone()).await?
+        };
 
+        // ---- SSH 핸드셰이크 (서버 식별 문자열 수신 시 KeyExchange, 키 교환 완료 시 Encrypted) ----
         self.transition(ConnectionState::VersionExchange)?;
-        self.transition(ConnectionState::KeyExchange)?;
+        let link = self.link.clone();
+        let on_server_id = move |server_id: &str| {
+            if let Err(e) = link.transition(ConnectionState::KeyExchange { server_id: server_id.to_string() }) {
+                tracing::warn!("[session] {}", e);
+            }
+        };
+        let (mut ssh, hello) = with_timeout(
+            timeouts.handshake, || self.state(),
+            handshake(russh_config, stream, handler, Box::new(on_server_id)),
+        ).await?;
+        let (negotiated, encrypted) = {
+            let hello = hello.lock().unwrap();
+            (hello.negotiated.clone(), hello.encrypted_state())
+        };
+        if let Some(negotiated) = &negotiated {
+            tracing::info!("[session] negotiated {}", negotiated);
+        }
+        self.negotiated = negotiated;
+        self.transition(encrypted)?;
 
         // ---- 인증 ----
-        self.transition(ConnectionState::Encrypted)?;
         self.transition(ConnectionState::Authenticating)?;
+        with_timeout(
+            timeouts.auth, || self.state(),
+            authenticate(&mut ssh, &config.username, &config.auth_methods, self.prompter.as_deref()),
+        ).await?;
+        self.transition(ConnectionState::Authenticated)?;
+
+        // ---- 채널 + SFTP ----
+        self.transition(ConnectionState::ChannelOpening)?;
+        let (sftp, channel, sftp_hello) = with_timeout(timeouts.sftp_init, || self.state(), open_sftp(&ssh)).await?;
+        *self.link.sftp_channel.lock().unwrap() = Some(channel);
 
-        let authed = match &config.auth {
-            AuthMethod::Password(pw) => {
-                ssh.authenticate_password(&config.username, pw)
-                    .await
-                    .map_err(|e| Error::Auth(e.to_string()))?
+        let hello = hello.lock().unwrap().clone();
+        self.server_info = Some(ServerInfo {
+            ssh_version: hello.version.unwrap_or_default(),
+            banner: hello.banner,
+            host_key_type: hello.host_key_type.unwrap_or_default(),
+            fingerprint: hello.fingerprint.unwrap_or_default(),
+            algorithms: hello.negotiated,
+            sftp_version: sftp_hello.as_ref().map(|h| h.version).unwrap_or(3),
+            extensions: sftp_hello.map(|h| h.extensions).unwrap_or_default(),
+        });
+        // 확장 요청 / 이름 조회용 채널 (열지 못하면 rename은 표준 SSH_FXP_RENAME, chown은 숫자 id만)
+        let extensions = self.server_info.as_ref().map(|info| info.extensions.clone()).unwrap_or_default();
+        match with_timeout(timeouts.sftp_init, || self.state(), open_raw_sftp(&ssh)).await {
+            Ok(session) => self.raw = Some(Arc::new(RawChannel { session, extensions })),
+            Err(e) => tracing::warn!("[session] cannot open raw SFTP channel: {}", e),
+        }
+        self.ssh = Some(ssh);
+        self.transition(ConnectionState::SftpReady)?;
+
+        tracing::info!("[session] SFTP ready");
+        Ok(self.track(sftp))
+    }
+
+    /// 같은 SSH 연결에 SFTP 채널 추가 (재인증 없음, 예: 목록 조회용 1개 + 전송용 N개)
+    ///
+    /// SftpReady 상태에서만 가능, timeouts.sftp_init 적용
+    /// 추가 채널은 reconnect() 후 다시 열리지 않음 (새 연결에서 다시 호출)
+    pub async fn open_channel(&mut self) -> Result<SftpClient> {
+        let ssh = match &self.ssh {
+            Some(ssh) if self.is_connected() => ssh,
+            _ => return Err(Error::Protocol(format!("cannot open SFTP channel in {:?}", self.state()))),
+        };
+        let limit = self.config.as_ref().and_then(|c| c.timeouts.sftp_init);
+        let (sftp, channel, _) = with_timeout(limit, || self.state(), open_sftp(ssh)).await?;
+        tracing::info!("[session] SFTP channel {} opened", channel);
+        Ok(self.track(sftp))
+    }
+
+    /// 채널 목록에 등록 후 SftpClient로 (닫힌 채널은 목록에서 정리)
+    fn track(&mut self, sftp: RusshSftpSession) -> SftpClient {
+        let sftp = Arc::new(sftp);
+        self.channels.retain(|c| c.strong_count() > 0);
+        self.channels.push(Arc::downgrade(&sftp));
+        SftpClient::with_raw(sftp, self.raw.clone())
+    }
+
+    /// 연결 종료: SSH_MSG_DISCONNECT 전송 (대상 서버 → 점프 호스트 역순)
+    ///
+    /// Disconnecting → Disconnected 전이, 이미 끊긴 연결이어도 상태는 Disconnected로 정리
+    /// 이후 기존 SftpClient는 사용 불가
+    pub async fn disconnect(&mut self) -> Result<()> {
+        if matches!(self.state(), ConnectionState::Disconnected) {
+            return Ok(());
+        }
+        self.transition(ConnectionState::Disconnecting)?;
+        self.close_handles().await;
+        self.transition(ConnectionState::Disconnected)?;
+        tracing::info!("[session] disconnected");
+        Ok(())
+    }
+
+    /// 끊긴 연결 재수립 (마지막 connect()의 ConnectConfig로 연결 + 인증 재실행)
+    ///
+    /// config.reconnect 정책에 따라 지수 백오프로 재시도, 재시도 불가 에러(인증 실패 등)는 즉시 반환
+    /// working_dir: 이전 작업 디렉토리 → 새 연결에서 realpath로 확인, 없어졌으면 홈 디렉토리
+    /// 반환: (새 SftpClient, 복원된 작업 디렉토리), 기존 SftpClient는 폐기
+    pub async fn reconnect(&mut self, working_dir: Option<&str>) -> Result<(SftpClient, String)> {
+        let config = self.config.clone()
+            .ok_or_else(|| Error::Protocol("reconnect requested before connect".to_string()))?;
+        let policy = config.reconnect.clone();
+        if policy.max_attempts == 0 {
+            return Err(Error::Protocol("reconnect is disabled".to_string()));
+        }
+
+        // 죽은 연결 정리 (상태 전이 없이)
+        self.close_handles().await;
+
+        let mut attempt = 0;
+        loop {
+            attempt += 1;
+            self.transition(ConnectionState::Reconnecting { attempt })?;
+            tracing::info!("[session] reconnecting to {} (attempt {}/{})", config.addr(), attempt, policy.max_attempts);
+
+            let e = match self.connect(&config).await {
+                Ok(sftp) => {
+                    let dir = restore_working_dir(&sftp, working_dir).await;
+                    tracing::info!("[session] reconnected, working directory: {}", dir);
+                    return Ok((sftp, dir));
+                }
+                Err(e) => e,
+            };
+            if attempt >= policy.max_attempts || !e.is_retryable() {
+                return Err(e);
+            }
+
+            let delay = policy.delay(attempt);
+            tracing::warn!("[session] reconnect attempt {} failed: {} (retry in {:.1}s)", attempt, e, delay.as_secs_f64());
+            tokio::time::sleep(delay).await;
+        }
+    }
+
+    /// SFTP 채널 종료 → 대상 서버 → 점프 호스트 역순으로 SSH_MSG_DISCONNECT 전송 후 핸들 정리
+    async fn close_handles(&mut self) {
+        for sftp in self.channels.drain(..).filter_map(|c| c.upgrade()) {
+            if let Err(e) = sftp.close().await {
+                tracing::debug!("[session] close SFTP channel: {}", e);
             }
-            AuthMethod::PublicKey { .. } => {
-                return Err(Error::Auth("PublicKey auth not implemented".to_string()));
+        }
+        if let Some(raw) = self.raw.take() {
+            let _ = raw.session.close_session();
+        }
+        let handles = self.ssh.take().into_iter().chain(self.jumps.drain(..).rev());
+        disconnect_all(handles).await;
+        *self.link.sftp_channel.lock().unwrap() = None;
+    }
+
+    /// 첫 전송 스트림 (대상 서버 또는 첫 hop)
+    ///
+    /// config.proxy가 있으면 프록시 경유, 없으면 TCP 직접 연결 (timeouts.tcp_connect 적용)
+    async fn open_transport(
+        &self,
+        config: &ConnectConfig,
+        host: &str,
+        port: u16,
+        username: &str,
+    ) -> Result<Box<dyn ProxyStream>> {
+        let connect = async {
+            match &config.proxy {
+                None => Ok(Box::new(TcpStream::connect((host, port)).await?) as Box<dyn ProxyStream>),
+                Some(proxy) => proxy::connect(proxy, host, port, username).await,
             }
         };
+        with_timeout(config.timeouts.tcp_connect, || ConnectionState::TcpConnecting, connect).await
+    }
 
-        if !authed {
-            self.transition_to_error("Authentication failed".to_string());
-            return Err(Error::Auth("Authentication failed".to_string()));
+    /// 점프 호스트를 순서대로 경유해 대상 서버까지의 전송 스트림 생성
+    ///
+    /// hop 1은 TCP 직접 연결, 이후 hop과 대상 서버는 이전 hop의 direct-tcpip 채널 위에서 핸드셰이크
+    /// 실패는 Error::JumpHost로 감싸 어느 hop에서 실패했는지 표시
+    /// hop 핸들은 성공했을 때만 self.jumps로 옮기고, 실패하면 이미 연결된 hop을 역순으로 닫음
+    async fn connect_via_jumps(
+        &mut self,
+        config: &ConnectConfig,
+        russh_config: Arc<client::Config>,
+    ) -> Result<Box<dyn ProxyStream>> {
+        let total = config.jump_hosts.len();
+        let mut hops: Vec<client::Handle<ClientHandler>> = Vec::with_capacity(total);
+
+        for (idx, jump) in config.jump_hosts.iter().enumerate() {
+            match self.connect_hop(idx + 1, total, jump, config, russh_config.clone(), hops.last()).await {
+                Ok(ssh) => hops.push(ssh),
+                Err(e) => {
+                    disconnect_all(hops.into_iter().rev()).await;
+                    return Err(e);
+                }
+            }
         }
-        self.transition(ConnectionState::Authenticated)?;
 
-        // ---- 채널 + SFTP ----
-        self.transition(ConnectionState::ChannelOpening)?;
+        // 마지막 hop → 대상 서버
+        let last = hops.last().expect("jump_hosts is not empty");
+        tracing::info!("[session] connecting to {} via jump host #{}", config.addr(), total);
+        match open_direct_tcpip(last, &config.host, config.port, config.timeouts.tcp_connect).await {
+            Ok(stream) => {
+                self.jumps = hops;
+                Ok(stream)
+            }
+            Err(e) => {
+                disconnect_all(hops.into_iter().rev()).await;
+                Err(self.hop_failed(total, total, &config.jump_hosts[total - 1], ConnectionState::Authenticated, e))
+            }
+        }
+    }
 
-        let channel = ssh.channel_open_session()
-            .await
-            .map_err(|e| Error::Protocol(e.to_string()))?;
+    /// 점프 호스트 하나 연결 + 인증 (prev: 이전 hop, None이면 TCP 직접 연결)
+    async fn connect_hop(
+        &self,
+        hop: usize,
+        total: usize,
+        jump: &JumpHost,
+        config: &ConnectConfig,
+        russh_config: Arc<client::Config>,
+        prev: Option<&client::Handle<ClientHandler>>,
+    ) -> Result<client::Handle<ClientHandler>> {
+        let timeouts = &config.timeouts;
+        let handler = self.handler(&jump.host, jump.port, config);
 
-        channel.request_subsystem(true, "sftp")
-            .await
-            .map_err(|e| Error::Protocol(e.to_string()))?;
+        // hop 진행 상태 (observer 알림 + 실패 시 어느 단계였는지 표시)
+        let phase = Arc::new(Mutex::new(ConnectionState::TcpConnecting));
+        let notify = {
+            let (link, phase, addr) = (self.link.clone(), phase.clone(), jump.addr());
+            Arc::new(move |state: ConnectionState| {
+                link.observer.on_hop_state_changed(hop, total, &addr, &state);
+                *phase.lock().unwrap() = state;
+            })
+        };
+        let current = || phase.lock().unwrap().clone();
+        let failed = |e| self.hop_failed(hop, total, jump, current(), e);
+
+        notify(ConnectionState::TcpConnecting);
+        tracing::info!("[session] connecting to jump host #{} {}", hop, jump.addr());
+        let stream = match prev {
+            None => self.open_transport(config, &jump.host, jump.port, &jump.username).await,
+            Some(prev) => open_direct_tcpip(prev, &jump.host, jump.port, timeouts.tcp_connect).await,
+        }.map_err(failed)?;
+
+        notify(ConnectionState::VersionExchange);
+        let on_server_id = {
+            let notify = notify.clone();
+            move |server_id: &str| notify(ConnectionState::KeyExchange { server_id: server_id.to_string() })
+        };
+        let (mut ssh, hello) = with_timeout(
+            timeouts.handshake, current,
+            handshake(russh_config, stream, handler, Box::new(on_server_id)),
+        ).await.map_err(failed)?;
+        let encrypted = hello.lock().unwrap().encrypted_state();
+        notify(encrypted);
+
+        notify(ConnectionState::Authenticating);
+        with_timeout(
+            timeouts.auth, current,
+            authenticate(&mut ssh, &jump.username, &jump.auth_methods, self.prompter.as_deref()),
+        ).await.map_err(failed)?;
+        notify(ConnectionState::Authenticated);
 
-        let sftp = RusshSftpSession::new(channel.into_stream())
+        Ok(ssh)
+    }
+
+    /// hop 실패 → observer에 Error 알림 + Error::JumpHost로 감쌈
+    fn hop_failed(&self, hop: usize, total: usize, jump: &JumpHost, state: ConnectionState, source: Error) -> Error {
+        let e = Error::JumpHost { hop, addr: jump.addr(), source: Box::new(source) };
+        self.link.observer.on_hop_state_changed(hop, total, &jump.addr(), &ConnectionState::Error {
+            state: Box::new(state),
+            message: e.to_string(),
+        });
+        e
+    }
+}
+
+/// 세션 채널 → sftp 서브시스템 → SFTP 초기화
+///
+/// 반환: (SFTP 세션, 채널 id, SSH_FXP_VERSION — 프로토콜 버전, 확장 목록)
+async fn open_sftp(ssh: &client::Handle<ClientHandler>) -> Result<(RusshSftpSession, ChannelId, Option<SftpHello>)> {
+    let channel = open_subsystem(ssh).await?;
+    let id = channel.id();
+    let (stream, hello) = handshake::sftp_tap(Box::new(channel.into_stream()));
+    let sftp = RusshSftpSession::new(stream)
+        .await
+        .map_err(Error::from)?;
+    let hello = hello.lock().unwrap().clone();
+    Ok((sftp, id, hello))
+}
+
+/// 확장 요청 / longname 조회용 SFTP 채널 (RawSftpSession, SSH_FXP_INIT까지)
+async fn open_raw_sftp(ssh: &client::Handle<ClientHandler>) -> Result<RawSftpSession> {
+    let channel = open_subsystem(ssh).await?;
+    let raw = RawSftpSession::new(channel.into_stream());
+    raw.init()
+        .await
+        .map_err(Error::from)?;
+    Ok(raw)
+}
+
+/// 세션 채널 열고 sftp 서브시스템 요청
+async fn open_subsystem(ssh: &client::Handle<ClientHandler>) -> Result<russh::Channel<client::Msg>> {
+    let channel = ssh.channel_open_session()
+        .await
+        .map_err(Error::from)?;
+
+    channel.request_subsystem(true, "sftp")
+        .await
+        .map_err(Error::from)?;
+    Ok(channel)
+}
+
+/// ConnectConfig → russh client::Config (keepalive / 알고리즘 우선순위)
+fn russh_config(config: &ConnectConfig) -> Result<client::Config> {
+    Ok(client::Config {
+        keepalive_interval: config.keepalive.interval,
+        keepalive_max: config.keepalive.max_missed,
+        preferred: algorithms::preferred(&config.algorithms)?,
+        ..Default::default()
+    })
+}
+
+/// SSH 핸드셰이크 (버전 교환 → 키 교환 → 호스트키 확인) + 협상 결과 계산
+///
+/// 반환: (연결, 서버 정보 — 이후 인증 중 배너도 여기에 기록됨)
+/// 공통 알고리즘이 없으면 서버가 제시한 목록을 에러 메시지에 포함
+async fn handshake(
+    russh_config: Arc<client::Config>,
+    stream: Box<dyn ProxyStream>,
+    mut handler: ClientHandler,
+    on_server_id: OnServerId,
+) -> Result<(client::Handle<ClientHandler>, Arc<Mutex<ServerHello>>)> {
+    let pref = russh_config.preferred.clone();
+    let (stream, hello) = handshake::tap(stream, on_server_id);
+    handler.hello = Some(hello.clone());
+    let server = || hello.lock().unwrap().algorithms.clone();
+
+    match client::connect_stream(russh_config, stream, handler).await {
+        Ok(ssh) => {
+            let negotiated = server().and_then(|s| algorithms::negotiate(&pref, &s));
+            hello.lock().unwrap().negotiated = negotiated;
+            Ok((ssh, hello))
+        }
+        Err(Error::Algorithm(message)) => Err(Error::Algorithm(
+            server().and_then(|s| algorithms::mismatch(&pref, &s)).unwrap_or(message),
+        )),
+        Err(e) => Err(e),
+    }
+}
+
+/// 제한 시간 안에 fut 완료, 만료 시 Error::Timeout (state: 만료 시점의 상태)
+async fn with_timeout<T>(
+    limit: Option<Duration>,
+    state: impl FnOnce() -> ConnectionState,
+    fut: impl Future<Output = Result<T>>,
+) -> Result<T> {
+    match limit {
+        None => fut.await,
+        Some(after) => match tokio::time::timeout(after, fut).await {
+            Ok(result) => result,
+            Err(_) => Err(Error::Timeout { state: state(), after }),
+        },
+    }
+}
+
+/// via 연결에서 host:port로 direct-tcpip 채널을 열어 스트림으로 (timeouts.tcp_connect 적용)
+async fn open_direct_tcpip(
+    via: &client::Handle<ClientHandler>,
+    host: &str,
+    port: u16,
+    limit: Option<Duration>,
+) -> Result<Box<dyn ProxyStream>> {
+    let open = async {
+        via.channel_open_direct_tcpip(host, port as u32, "127.0.0.1", 0)
             .await
-            .map_err(|e| Error::Protocol(e.to_string()))?;
+            .map(|channel| Box::new(channel.into_stream()) as Box<dyn ProxyStream>)
+            .map_err(|e| match Error::from(e) {
+                Error::Io(e)       => Error::Io(std::io::Error::new(e.kind(), format!("cannot reach {}:{}: {}", host, port, e))),
+                Error::Protocol(m) => Error::Protocol(format!("cannot reach {}:{}: {}", host, port, m)),
+                e => e,
+            })
+    };
+    with_timeout(limit, || ConnectionState::TcpConnecting, open).await
+}
 
-        self.transition(ConnectionState::SftpReady)?;
+/// 재연결 후 작업 디렉토리 복원 (없어졌으면 홈 디렉토리, 그것도 실패하면 ".")
+async fn restore_working_dir(sftp: &SftpClient, working_dir: Option<&str>) -> String {
+    if let Some(dir) = working_dir {
+        match sftp.realpath(dir).await {
+            Ok(path) => return path,
+            Err(e) => tracing::warn!("[session] cannot restore working directory {}: {}", dir, e),
+        }
+    }
+    sftp.realpath(".").await.unwrap_or_else(|_| ".".to_string())
+}
 
-        tracing::info!("[session] SFTP ready");
-        Ok(SftpClient::new(sftp))
+/// SSH_MSG_DISCONNECT 전송 후 닫힐 때까지 대기 (주어진 순서대로, 이미 닫힌 핸들은 건너뜀)
+async fn disconnect_all(handles: impl IntoIterator<Item = client::Handle<ClientHandler>>) {
+    for ssh in handles {
+        if ssh.is_closed() {
+            continue;
+        }
+        if let Err(e) = ssh.disconnect(Disconnect::ByApplication, "", "en").await {
+            tracing::debug!("[session] disconnect: {}", e);
+        }
+        wait_closed(&ssh).await;
+    }
+}
+
+/// 세션 태스크가 DISCONNECT를 보내고 종료할 때까지 대기 (최대 1초)
+async fn wait_closed(ssh: &client::Handle<ClientHandler>) {
+    let deadline = tokio::time::Instant::now() + std::time::Duration::from_secs(1);
+    while !ssh.is_closed() && tokio::time::Instant::now() < deadline {
+        tokio::time::sleep(std::time::Duration::from_millis(10)).await;
     }
 }
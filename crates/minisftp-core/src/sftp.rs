@@ -1,14 +1,25 @@
 // miniSFTP SFTP (russh-sftp 기반)
 // author: kodeholic (powered by Claude)
 //
-// FileEntry    : 리모트/로컬 공통 파일 엔트리
+// FileEntry    : 리모트/로컬 공통 파일 엔트리 (ls, stat, lstat, local_ls)
+// FileType     : 파일 종류 (mode의 S_IFMT 비트)
 // ProgressInfo : 전송 진척 정보 (콜백으로 전달)
 // TransferResult : get/put 결과
-// SftpClient   : ls, get, put, mkdir, rm
+// SymlinkMode  : get 시 심볼릭 링크 처리 (따라가기 / 링크로 보존)
+// TransferOptions : get/put 옵션 (심볼릭 링크, 권한 보존)
+// RemoveProgress / RemoveResult : remove_all 진척 / 결과
+// ModeSpec     : chmod 모드 (8진수 / 기호 "u+x,g-w")
+// Owner        : chown / chgrp 대상 (숫자 id / 이름)
+// SftpClient   : ls, stat, lstat, exists, is_dir, get, put, mkdir, rm, rmdir, rename, remove_all,
+//                chmod, chown, set_times, touch (+ 재귀), symlink, read_link, hardlink
 
-use russh_sftp::client::SftpSession;
-use russh_sftp::protocol::OpenFlags;
-use crate::error::{Error, Result};
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use russh_sftp::client::error::Error as RusshSftpError;
+use russh_sftp::client::{RawSftpSession, SftpSession};
+use russh_sftp::protocol::{FileAttributes, OpenFlags, Packet, StatusCode};
+use crate::error::{Error, Result, SftpErrorKind};
 use crate::utils::{mtime_str, permission_str};
 
 // CancellationToken을 외부에서 쓸 수 있도록 re-export
@@ -16,30 +27,141 @@ pub use tokio_util::sync::CancellationToken;
 
 const CHUNK_SIZE: usize = 64 * 1024; // 64KB
 
+/// 대상이 있어도 원자적으로 교체하는 rename 확장 (SSH_FXP_VERSION에 "1"로 광고)
+pub(crate) const POSIX_RENAME: &str = "posix-rename@openssh.com";
+
+/// uid / gid → 사용자 / 그룹 이름 조회 확장 (SSH_FXP_VERSION에 "1"로 광고)
+const USERS_GROUPS_BY_ID: &str = "users-groups-by-id@openssh.com";
+
+/// users-groups-by-id로 주변 항목 외에 함께 물어보는 id (시스템 / 일반 계정 대역, nobody)
+const LOOKUP_IDS: std::ops::RangeInclusive<u32> = 0..=1999;
+const NOBODY_ID: u32 = 65534;
+
 // ── 공통 타입 ─────────────────────────────────────────────────────────────────
 
+/// 파일 종류 (mode의 S_IFMT 비트, 권한 정보가 없으면 Unknown)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FileType {
+    File,
+    Dir,
+    Symlink,
+    BlockDevice,
+    CharDevice,
+    Fifo,
+    Socket,
+    Unknown,
+}
+
+impl FileType {
+    pub fn from_mode(mode: u32) -> Self {
+        match mode & 0o170000 {
+            0o100000 => FileType::File,
+            0o040000 => FileType::Dir,
+            0o120000 => FileType::Symlink,
+            0o060000 => FileType::BlockDevice,
+            0o020000 => FileType::CharDevice,
+            0o010000 => FileType::Fifo,
+            0o140000 => FileType::Socket,
+            _        => FileType::Unknown,
+        }
+    }
+
+    /// 리모트 속성의 종류 (russh-sftp의 is_dir()은 비트 포함 여부만 봐서 소켓 / 블록 장치도 true)
+    fn of(attrs: &FileAttributes) -> Self {
+        attrs.permissions.map(FileType::from_mode).unwrap_or(FileType::Unknown)
+    }
+
+    pub fn is_file(&self) -> bool    { *self == FileType::File }
+    pub fn is_dir(&self) -> bool     { *self == FileType::Dir }
+    pub fn is_symlink(&self) -> bool { *self == FileType::Symlink }
+}
+
 /// 파일/디렉토리 엔트리 (리모트/로컬 공통)
+///
+/// 심볼릭 링크: file_type은 링크 자체(Symlink), target_type은 대상 종류, 나머지 속성은 링크 자체
 pub struct FileEntry {
     pub name: String,
-    pub is_dir: bool,
+    pub file_type: FileType,
+    /// 심볼릭 링크 대상 종류 (링크가 아니거나 깨진 링크면 None)
+    pub target_type: Option<FileType>,
+    /// 심볼릭 링크 대상 (readlink, 링크가 아니거나 읽기 실패 시 None)
+    pub link_target: Option<String>,
     pub size: u64,
+    /// st_mode 그대로 (종류 비트 포함, 서버가 보내지 않으면 None)
     pub permissions: Option<u32>,
     pub uid: Option<u32>,
     pub gid: Option<u32>,
+    pub atime: Option<u64>,
     pub mtime: Option<u64>,
+    /// SFTP 확장 속성 (extended_type, extended_data)
+    ///
+    /// russh-sftp 2.4는 ATTRS의 확장 속성을 파싱하지 않고 버림 → 리모트 항목은 현재 항상 비어 있음
+    pub extended: Vec<(String, String)>,
 }
 
 impl FileEntry {
+    /// 디렉토리이거나 디렉토리를 가리키는 심볼릭 링크 (cd 가능)
+    pub fn is_dir(&self) -> bool {
+        self.file_type.is_dir() || self.target_type == Some(FileType::Dir)
+    }
+
+    /// 일반 파일이거나 일반 파일을 가리키는 심볼릭 링크
+    pub fn is_file(&self) -> bool {
+        self.file_type.is_file() || self.target_type == Some(FileType::File)
+    }
+
+    pub fn is_symlink(&self) -> bool {
+        self.file_type.is_symlink()
+    }
+
+    /// 권한 비트만 (0o7777 범위)
+    pub fn mode(&self) -> Option<u32> {
+        self.permissions.map(|p| p & 0o7777)
+    }
+
     pub fn permission_str(&self) -> String {
         match self.permissions {
             Some(p) => permission_str(p),
-            None    => if self.is_dir { "d?????????" } else { "----------" }.to_string(),
+            None    => match self.file_type {
+                FileType::Dir     => "d?????????",
+                FileType::Symlink => "l?????????",
+                _                 => "----------",
+            }.to_string(),
         }
     }
 
+    pub fn atime_str(&self) -> String {
+        self.atime.map(mtime_str).unwrap_or_else(|| "?".to_string())
+    }
+
     pub fn mtime_str(&self) -> String {
         self.mtime.map(mtime_str).unwrap_or_else(|| "?".to_string())
     }
+
+    /// 목록 표시용 이름 (심볼릭 링크면 "name -> target")
+    pub fn display_name(&self) -> String {
+        match &self.link_target {
+            Some(target) => format!("{} -> {}", self.name, target),
+            None => self.name.clone(),
+        }
+    }
+
+    /// 리모트 속성 → FileEntry (링크 대상 정보는 호출측에서 채움)
+    fn from_attrs(name: &str, attrs: &FileAttributes) -> Self {
+        FileEntry {
+            name: name.to_string(),
+            file_type: FileType::of(attrs),
+            target_type: None,
+            link_target: None,
+            size: attrs.size.unwrap_or(0),
+            permissions: attrs.permissions,
+            uid: attrs.uid,
+            gid: attrs.gid,
+            atime: attrs.atime.map(|t| t as u64),
+            mtime: attrs.mtime.map(|t| t as u64),
+            extended: Vec::new(),
+        }
+    }
 }
 
 /// 전송 진척 정보
@@ -63,10 +185,7 @@ impl ProgressInfo {
         } else { 0 }
     }
     pub fn eta_secs(&self) -> Option<u64> {
-        let speed = self.speed();
-        if speed > 0 {
-            Some(self.total.saturating_sub(self.transferred) / speed)
-        } else { None }
+        self.total.saturating_sub(self.transferred).checked_div(self.speed())
     }
 }
 
@@ -76,56 +195,349 @@ pub enum TransferResult {
     Resumed(u64),   // 이어받기/이어올리기 완료 → 총 바이트
     Completed(u64), // 새로 전송 완료 → 총 바이트
     Cancelled(u64), // 사용자 취소 → 취소 시점까지 전송된 바이트
+    Linked(String), // 심볼릭 링크로 보존 (SymlinkMode::Preserve) → 링크 대상
+}
+
+/// get 대상이 심볼릭 링크일 때 처리 방식
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SymlinkMode {
+    /// 링크 대상 파일 내용을 받음 (OpenSSH sftp와 같음)
+    #[default]
+    Follow,
+    /// 로컬에 같은 대상을 가리키는 심볼릭 링크 생성 (unix 전용)
+    Preserve,
+}
+
+/// get/put 옵션
+///
+/// 전송 완료 후 원본 atime / mtime은 항상 대상에 적용 (다음 전송의 Skipped 판별 기준)
+#[derive(Debug, Clone, Copy, Default)]
+pub struct TransferOptions {
+    /// get 대상이 심볼릭 링크일 때 처리 (put은 링크를 따라감)
+    pub symlinks: SymlinkMode,
+    /// 권한 비트도 대상에 적용 (sftp -p)
+    pub preserve_mode: bool,
+}
+
+/// remove_all 진척 정보 (항목 하나를 지울 때마다 전달)
+#[derive(Debug, Clone)]
+pub struct RemoveProgress {
+    pub files: u64,
+    pub dirs: u64,
+    /// 방금 지운 경로
+    pub path: String,
+}
+
+/// remove_all 결과 (지운 파일 / 디렉토리 수)
+pub enum RemoveResult {
+    Completed { files: u64, dirs: u64 },
+    Cancelled { files: u64, dirs: u64 }, // 사용자 취소 → 취소 시점까지 지운 수
+}
+
+/// 재귀 속성 변경 결과 (변경한 항목 수)
+pub enum WalkResult {
+    Completed(u64),
+    Cancelled(u64), // 사용자 취소 → 취소 시점까지 변경한 수
+}
+
+/// chmod 모드: 8진수("755") 또는 기호("u+x,g-w", "a=rX", "go=u")
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ModeSpec {
+    Octal(u32),
+    Symbolic(Vec<ModeClause>),
+}
+
+/// 기호 모드의 절 하나 ("ug+rw" → who: 0o6770, ops: [('+', rw)])
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ModeClause {
+    /// 대상 비트 마스크 (u: 0o4700, g: 0o2070, o: 0o1007, 생략 / a: 0o7777)
+    who: u32,
+    ops: Vec<(char, ModePerms)>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum ModePerms {
+    /// "rwxXst" 조합
+    Bits(String),
+    /// "=u"처럼 다른 대상의 현재 권한 복사 (6: u, 3: g, 0: o)
+    Copy(u32),
+}
+
+impl std::str::FromStr for ModeSpec {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let invalid = || Error::Protocol(format!("invalid mode: {}", s));
+        if !s.is_empty() && s.len() <= 4 && s.chars().all(|c| ('0'..='7').contains(&c)) {
+            return u32::from_str_radix(s, 8).map(ModeSpec::Octal).map_err(|_| invalid());
+        }
+
+        let mut clauses = Vec::new();
+        for clause in s.split(',') {
+            let op_at = clause.find(['+', '-', '=']).ok_or_else(invalid)?;
+            let (who_str, mut rest) = clause.split_at(op_at);
+            let mut who = 0;
+            for c in who_str.chars() {
+                who |= match c {
+                    'u' => 0o4700,
+                    'g' => 0o2070,
+                    'o' => 0o1007,
+                    'a' => 0o7777,
+                    _ => return Err(invalid()),
+                };
+            }
+            if who == 0 {
+                who = 0o7777;
+            }
+
+            let mut ops = Vec::new();
+            while let Some(op) = rest.chars().next().filter(|c| matches!(c, '+' | '-' | '=')) {
+                let perms_end = rest[1..].find(['+', '-', '=']).map(|i| i + 1).unwrap_or(rest.len());
+                let perms = &rest[1..perms_end];
+                let perms = match perms {
+                    "u" => ModePerms::Copy(6),
+                    "g" => ModePerms::Copy(3),
+                    "o" => ModePerms::Copy(0),
+                    _ if perms.chars().all(|c| "rwxXst".contains(c)) => ModePerms::Bits(perms.to_string()),
+                    _ => return Err(invalid()),
+                };
+                ops.push((op, perms));
+                rest = &rest[perms_end..];
+            }
+            if !rest.is_empty() || ops.is_empty() {
+                return Err(invalid());
+            }
+            clauses.push(ModeClause { who, ops });
+        }
+        Ok(ModeSpec::Symbolic(clauses))
+    }
+}
+
+impl ModeSpec {
+    /// 현재 모드(타입 비트 포함 가능)에 적용한 새 권한 (0o7777 범위)
+    ///
+    /// X: 디렉토리이거나 이미 누군가 실행 권한이 있을 때만 x, umask는 적용하지 않음
+    pub fn apply(&self, current: u32, is_dir: bool) -> u32 {
+        let clauses = match self {
+            ModeSpec::Octal(mode) => return mode & 0o7777,
+            ModeSpec::Symbolic(clauses) => clauses,
+        };
+
+        let mut mode = current & 0o7777;
+        for clause in clauses {
+            for (op, perms) in &clause.ops {
+                let bits = match perms {
+                    ModePerms::Copy(shift) => ((mode >> shift) & 0o7) * 0o111,
+                    ModePerms::Bits(chars) => chars.chars().map(|c| match c {
+                        'r' => 0o444,
+                        'w' => 0o222,
+                        'x' => 0o111,
+                        'X' if is_dir || mode & 0o111 != 0 => 0o111,
+                        's' => 0o6000,
+                        't' => 0o1000,
+                        _ => 0,
+                    }).fold(0, |acc, b| acc | b),
+                };
+                let bits = bits & clause.who;
+                mode = match op {
+                    '+' => mode | bits,
+                    '-' => mode & !bits,
+                    // '=': 대상의 rwx(+ setuid/setgid/sticky)를 비운 뒤 설정
+                    _ => (mode & !clause.who) | bits,
+                };
+            }
+        }
+        mode
+    }
+}
+
+/// chown / chgrp 대상: 숫자 id("1000") 또는 사용자 / 그룹 이름("deploy")
+///
+/// 이름은 서버에서 id로 바꿔서 보냄 (SftpClient::chown 참고)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Owner {
+    Id(u32),
+    Name(String),
+}
+
+impl std::str::FromStr for Owner {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        if s.is_empty() || s.contains(char::is_whitespace) {
+            return Err(Error::Protocol(format!("invalid owner: '{}'", s)));
+        }
+        if s.chars().all(|c| c.is_ascii_digit()) {
+            return s.parse().map(Owner::Id)
+                .map_err(|_| Error::Protocol(format!("invalid owner id: {}", s)));
+        }
+        Ok(Owner::Name(s.to_string()))
+    }
+}
+
+impl std::fmt::Display for Owner {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Owner::Id(id)     => write!(f, "{}", id),
+            Owner::Name(name) => write!(f, "{}", name),
+        }
+    }
 }
 
 // ── SftpClient ────────────────────────────────────────────────────────────────
 
+/// 고수준 SftpSession으로 보낼 수 없는 요청용 SFTP 채널 (연결당 하나, 이 연결의 SftpClient가 공유)
+///
+/// SSH_FXP_EXTENDED 요청(posix-rename, users-groups-by-id)과
+/// longname이 필요한 READDIR(chown 이름 조회)에 사용
+pub(crate) struct RawChannel {
+    pub(crate) session: RawSftpSession,
+    /// 서버가 SSH_FXP_VERSION으로 광고한 확장 (이름, 데이터)
+    pub(crate) extensions: Vec<(String, String)>,
+}
+
+impl RawChannel {
+    fn supports(&self, name: &str) -> bool {
+        self.extensions.iter().any(|(n, data)| n == name && data == "1")
+    }
+}
+
+/// SFTP 채널 하나 (SftpSession::connect / open_channel로 생성, 채널마다 독립적으로 사용 가능)
+///
+/// clone은 같은 채널을 공유 (Arc), Send + Sync → 여러 태스크에서 동시에 요청 가능
+/// SFTP는 요청 id로 응답을 구분하므로 ls / stat이 진행 중인 get / put 뒤에 줄 서지 않음
+#[derive(Clone)]
 pub struct SftpClient {
-    sftp: SftpSession,
+    // SftpSession(minisftp)이 Weak로 추적 → disconnect 시 채널 종료
+    sftp: Arc<SftpSession>,
+    // 확장 요청 / longname 조회용 채널 (열지 못했으면 None → rename은 SSH_FXP_RENAME, chown은 숫자 id만)
+    raw: Option<Arc<RawChannel>>,
 }
 
+// 태스크 간 공유 가능 여부를 컴파일 시점에 확인
+const _: fn() = || {
+    fn assert_shareable<T: Clone + Send + Sync>() {}
+    assert_shareable::<SftpClient>();
+};
+
 impl SftpClient {
-    pub fn new(sftp: SftpSession) -> Self {
-        Self { sftp }
+    pub fn new(sftp: Arc<SftpSession>) -> Self {
+        Self { sftp, raw: None }
+    }
+
+    /// 확장 요청 채널과 함께 생성 (SftpSession(minisftp)::connect / open_channel)
+    pub(crate) fn with_raw(sftp: Arc<SftpSession>, raw: Option<Arc<RawChannel>>) -> Self {
+        Self { sftp, raw }
+    }
+
+    /// 채널 종료 (다른 채널과 SSH 연결은 유지, 이 채널을 공유하는 clone도 사용 불가)
+    pub async fn close(&self) -> Result<()> {
+        self.sftp.close().await
+            .map_err(Error::from)
     }
 
-    pub async fn ls(&mut self, path: &str) -> Result<Vec<FileEntry>> {
+    pub async fn ls(&self, path: &str) -> Result<Vec<FileEntry>> {
         let dir = self.sftp.read_dir(path)
             .await
-            .map_err(|e| Error::Protocol(e.to_string()))?;
+            .map_err(|e| Error::sftp(path, e))?;
 
         let mut entries: Vec<FileEntry> = dir.into_iter()
             .filter(|e| e.file_name() != "." && e.file_name() != "..")
-            .map(|e| {
-                let attrs = e.metadata();
-                let permissions = attrs.permissions;
-                let is_dir = permissions
-                    .map(|p| p & 0o170000 == 0o040000)
-                    .unwrap_or(false);
-                FileEntry {
-                    name: e.file_name().to_string(),
-                    is_dir,
-                    size: attrs.size.unwrap_or(0),
-                    permissions,
-                    uid: attrs.uid,
-                    gid: attrs.gid,
-                    mtime: attrs.mtime.map(|t| t as u64),
-                }
-            })
+            .map(|e| FileEntry::from_attrs(&e.file_name(), &e.metadata()))
             .collect();
 
-        entries.sort_by(|a, b| b.is_dir.cmp(&a.is_dir).then(a.name.cmp(&b.name)));
+        // READDIR 속성은 lstat 기준 → 링크만 대상 경로 / 종류를 따로 조회
+        // (russh-sftp가 longname을 버려서 "name -> target"을 그대로 쓸 수 없음)
+        for entry in entries.iter_mut().filter(|e| e.is_symlink()) {
+            let link = format!("{}/{}", path.trim_end_matches('/'), entry.name);
+            self.resolve_link(&link, entry).await;
+        }
+
+        entries.sort_by(|a, b| b.is_dir().cmp(&a.is_dir()).then(a.name.cmp(&b.name)));
         Ok(entries)
     }
 
+    /// 경로 속성 (심볼릭 링크는 따라감, SSH_FXP_STAT)
+    pub async fn stat(&self, path: &str) -> Result<FileEntry> {
+        let attrs = self.sftp.metadata(path).await
+            .map_err(|e| Error::sftp(path, e))?;
+        Ok(FileEntry::from_attrs(file_name(path), &attrs))
+    }
+
+    /// 경로 속성 (심볼릭 링크 자체, SSH_FXP_LSTAT) → 링크면 link_target 포함
+    pub async fn lstat(&self, path: &str) -> Result<FileEntry> {
+        let attrs = self.sftp.symlink_metadata(path).await
+            .map_err(|e| Error::sftp(path, e))?;
+        let mut entry = FileEntry::from_attrs(file_name(path), &attrs);
+        if entry.is_symlink() {
+            self.resolve_link(path, &mut entry).await;
+        }
+        Ok(entry)
+    }
+
+    /// 경로가 있는지 (링크는 따라감 → 깨진 링크는 false, 파일 없음 외의 에러는 Err)
+    pub async fn exists(&self, path: &str) -> Result<bool> {
+        match self.stat(path).await {
+            Ok(_) => Ok(true),
+            Err(e) if e.sftp_kind() == Some(SftpErrorKind::NoSuchFile) => Ok(false),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// 디렉토리(또는 디렉토리를 가리키는 링크)인지, 없으면 false
+    pub async fn is_dir(&self, path: &str) -> Result<bool> {
+        match self.stat(path).await {
+            Ok(entry) => Ok(entry.is_dir()),
+            Err(e) if e.sftp_kind() == Some(SftpErrorKind::NoSuchFile) => Ok(false),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// 심볼릭 링크 대상 경로 (SSH_FXP_READLINK, 서버가 저장한 그대로 — 상대경로일 수 있음)
+    pub async fn read_link(&self, path: &str) -> Result<String> {
+        self.sftp.read_link(path).await
+            .map_err(|e| Error::sftp(path, e))
+    }
+
+    /// 심볼릭 링크 생성: link → target (ln -s target link)
+    ///
+    /// OpenSSH sftp-server는 SSH_FXP_SYMLINK 인자를 명세와 반대 순서(target, link)로 읽음
+    ///   → 대부분의 서버 / 클라이언트가 이 순서를 따르므로 같은 순서로 보냄
+    pub async fn symlink(&self, target: &str, link: &str) -> Result<()> {
+        self.sftp.symlink(target, link).await
+            .map_err(|e| Error::sftp(link, e))
+    }
+
+    /// 하드링크 생성: link → existing과 같은 파일 (hardlink@openssh.com 확장 필요)
+    pub async fn hardlink(&self, existing: &str, link: &str) -> Result<()> {
+        match self.sftp.hardlink(existing, link).await {
+            Ok(true) => Ok(()),
+            Ok(false) => Err(Error::Sftp {
+                kind: SftpErrorKind::OpUnsupported,
+                path: Some(link.to_string()),
+                message: "server does not support hardlink@openssh.com".to_string(),
+                source: None,
+            }),
+            Err(e) => Err(Error::sftp(link, e)),
+        }
+    }
+
+    /// 링크 항목에 대상 경로와 대상 종류 채움 (깨진 링크면 target_type은 None)
+    async fn resolve_link(&self, link: &str, entry: &mut FileEntry) {
+        entry.link_target = self.sftp.read_link(link).await.ok();
+        entry.target_type = self.sftp.metadata(link).await.ok().map(|target| FileType::of(&target));
+    }
+
     /// 리모트 파일 다운로드 (FileZilla 방식)
     ///
     /// cancel 토큰이 취소되면 현재 청크 완료 후 Cancelled(transferred) 반환
     /// remote_file을 명시적으로 drop해서 channel closed 경고 방지
+    /// 완료(Completed / Resumed) 후 리모트 시각(options.preserve_mode면 권한도)을 로컬 파일에 적용
     pub async fn get<F>(
-        &mut self,
+        &self,
         remote: &str,
         local: &str,
+        options: TransferOptions,
         on_progress: F,
         cancel: CancellationToken,
     ) -> Result<TransferResult>
@@ -134,8 +546,17 @@ impl SftpClient {
     {
         use tokio::io::{AsyncReadExt, AsyncSeekExt, AsyncWriteExt};
 
+        if options.symlinks == SymlinkMode::Preserve {
+            let attrs = self.sftp.symlink_metadata(remote).await
+                .map_err(|e| Error::sftp(remote, e))?;
+            if FileType::of(&attrs).is_symlink() {
+                let target = self.read_link(remote).await?;
+                return local_symlink(&target, local).map(|_| TransferResult::Linked(target));
+            }
+        }
+
         let remote_meta  = self.sftp.metadata(remote).await
-            .map_err(|e| Error::Protocol(e.to_string()))?;
+            .map_err(|e| Error::sftp(remote, e))?;
         let remote_size  = remote_meta.size.unwrap_or(0);
         let remote_mtime = remote_meta.mtime.unwrap_or(0) as u64;
 
@@ -155,11 +576,11 @@ impl SftpClient {
         let is_resume = offset > 0;
 
         let mut remote_file = self.sftp.open(remote).await
-            .map_err(|e| Error::Protocol(e.to_string()))?;
+            .map_err(|e| Error::sftp(remote, e))?;
 
         if offset > 0 {
             remote_file.seek(std::io::SeekFrom::Start(offset)).await
-                .map_err(|e| Error::Protocol(e.to_string()))?;
+                .map_err(|e| Error::remote_io(remote, e))?;
         }
 
         let mut local_file = tokio::fs::OpenOptions::new()
@@ -168,7 +589,7 @@ impl SftpClient {
             .write(!is_resume)
             .truncate(!is_resume)
             .open(local).await
-            .map_err(|e| Error::Io(e))?;
+            .map_err(|e| Error::local_io(local, e))?;
 
         let mut buf         = vec![0u8; CHUNK_SIZE];
         let mut transferred = offset;
@@ -183,13 +604,13 @@ impl SftpClient {
                     return Ok(TransferResult::Cancelled(transferred));
                 }
                 result = remote_file.read(&mut buf) => {
-                    result.map_err(|e| Error::Protocol(e.to_string()))?
+                    result.map_err(|e| Error::remote_io(remote, e))?
                 }
             };
             if n == 0 { break; }
 
             local_file.write_all(&buf[..n]).await
-                .map_err(|e| Error::Io(e))?;
+                .map_err(|e| Error::local_io(local, e))?;
 
             transferred += n as u64;
             on_progress(ProgressInfo {
@@ -198,6 +619,12 @@ impl SftpClient {
                 elapsed_secs: start.elapsed().as_secs_f64(),
             });
         }
+        drop(remote_file);
+
+        // 쓰기를 모두 반영한 뒤 시각 적용 (이후 쓰기가 mtime을 다시 바꾸지 않도록)
+        local_file.flush().await
+            .map_err(|e| Error::local_io(local, e))?;
+        set_local_attrs(local_file.into_std().await, local, &remote_meta, options.preserve_mode)?;
 
         if is_resume { Ok(TransferResult::Resumed(transferred)) }
         else         { Ok(TransferResult::Completed(transferred)) }
@@ -206,10 +633,13 @@ impl SftpClient {
     /// 로컬 파일 업로드 (FileZilla 방식)
     ///
     /// cancel 토큰이 취소되면 현재 청크 완료 후 Cancelled(transferred) 반환
+    /// 완료 후 로컬 시각(options.preserve_mode면 권한도)을 SETSTAT으로 리모트에 적용
+    ///   → 서버가 SETSTAT을 거부하면 시각은 경고만, preserve_mode는 에러
     pub async fn put<F>(
-        &mut self,
+        &self,
         local: &str,
         remote: &str,
+        options: TransferOptions,
         on_progress: F,
         cancel: CancellationToken,
     ) -> Result<TransferResult>
@@ -219,7 +649,7 @@ impl SftpClient {
         use tokio::io::{AsyncReadExt, AsyncSeekExt, AsyncWriteExt};
 
         let local_meta  = tokio::fs::metadata(local).await
-            .map_err(|e| Error::Io(e))?;
+            .map_err(|e| Error::local_io(local, e))?;
         let local_size  = local_meta.len();
         let local_mtime = local_meta.modified().ok()
             .and_then(|t| t.duration_since(std::time::UNIX_EPOCH).ok())
@@ -238,11 +668,11 @@ impl SftpClient {
         let is_resume = offset > 0;
 
         let mut local_file = tokio::fs::File::open(local).await
-            .map_err(|e| Error::Io(e))?;
+            .map_err(|e| Error::local_io(local, e))?;
 
         if offset > 0 {
             local_file.seek(std::io::SeekFrom::Start(offset)).await
-                .map_err(|e| Error::Io(e))?;
+                .map_err(|e| Error::local_io(local, e))?;
         }
 
         let mut remote_file = if is_resume {
@@ -250,7 +680,7 @@ impl SftpClient {
         } else {
             self.sftp.open_with_flags(remote,
                 OpenFlags::CREATE | OpenFlags::WRITE | OpenFlags::TRUNCATE).await
-        }.map_err(|e| Error::Protocol(e.to_string()))?;
+        }.map_err(|e| Error::sftp(remote, e))?;
 
         let mut buf         = vec![0u8; CHUNK_SIZE];
         let mut transferred = offset;
@@ -264,13 +694,13 @@ impl SftpClient {
                     return Ok(TransferResult::Cancelled(transferred));
                 }
                 result = local_file.read(&mut buf) => {
-                    result.map_err(|e| Error::Io(e))?
+                    result.map_err(|e| Error::local_io(local, e))?
                 }
             };
             if n == 0 { break; }
 
             remote_file.write_all(&buf[..n]).await
-                .map_err(|e| Error::Protocol(e.to_string()))?;
+                .map_err(|e| Error::remote_io(remote, e))?;
 
             transferred += n as u64;
             on_progress(ProgressInfo {
@@ -280,33 +710,667 @@ impl SftpClient {
             });
         }
 
+        // 남은 쓰기 응답 + SSH_FXP_CLOSE 확인 후 시각 적용
+        remote_file.shutdown().await
+            .map_err(|e| Error::remote_io(remote, e))?;
+        // 로컬 시각이 SFTP v3 범위(32비트 초)를 넘으면 SETSTAT 실패와 같이 처리
+        let atime = local_meta.accessed().ok()
+            .and_then(|t| t.duration_since(std::time::UNIX_EPOCH).ok())
+            .map_or(local_mtime, |d| d.as_secs());
+        let result = match (local_time(atime, local), local_time(local_mtime, local)) {
+            (Ok(atime), Ok(mtime)) => self.setstat(remote, FileAttributes {
+                atime: Some(atime),
+                mtime: Some(mtime),
+                permissions: local_mode(&local_meta).filter(|_| options.preserve_mode),
+                ..FileAttributes::empty()
+            }).await,
+            (Err(e), _) | (_, Err(e)) => Err(e),
+        };
+        if let Err(e) = result {
+            if options.preserve_mode {
+                return Err(e);
+            }
+            tracing::warn!("[sftp] cannot set times on {}: {}", remote, e);
+        }
+
         if is_resume { Ok(TransferResult::Resumed(transferred)) }
         else         { Ok(TransferResult::Completed(transferred)) }
     }
 
-    pub async fn mkdir(&mut self, path: &str) -> Result<()> {
+    pub async fn mkdir(&self, path: &str) -> Result<()> {
         self.sftp.create_dir(path).await
-            .map_err(|e| Error::Protocol(e.to_string()))?;
+            .map_err(|e| Error::sftp(path, e))?;
         Ok(())
     }
 
-    pub async fn rm(&mut self, path: &str) -> Result<()> {
+    /// 파일 삭제 (디렉토리는 rmdir / remove_all)
+    pub async fn rm(&self, path: &str) -> Result<()> {
         self.sftp.remove_file(path).await
-            .map_err(|e| Error::Protocol(e.to_string()))?;
+            .map_err(|e| Error::sftp(path, e))?;
         Ok(())
     }
 
+    /// 빈 디렉토리 삭제
+    pub async fn rmdir(&self, path: &str) -> Result<()> {
+        self.sftp.remove_dir(path).await
+            .map_err(|e| Error::sftp(path, e))?;
+        Ok(())
+    }
+
+    /// 이름 변경 / 이동
+    ///
+    /// 서버가 posix-rename@openssh.com을 지원하면 대상이 있어도 원자적으로 교체
+    /// 지원하지 않으면 표준 SSH_FXP_RENAME (대상이 있으면 서버에 따라 실패 — OpenSSH는 Failure)
+    pub async fn rename(&self, from: &str, to: &str) -> Result<()> {
+        match self.raw.as_deref().filter(|raw| raw.supports(POSIX_RENAME)) {
+            Some(raw) => posix_rename(&raw.session, from, to).await
+                .map_err(|e| Error::sftp(from, e)),
+            None => self.sftp.rename(from, to).await
+                .map_err(|e| Error::sftp(from, e)),
+        }
+    }
+
+    /// 디렉토리 트리 전체 삭제 (rm -r, 하위 항목 먼저)
+    ///
+    /// 심볼릭 링크는 따라가지 않고 링크만 삭제, 파일이면 그 파일만 삭제
+    /// cancel 토큰이 취소되면 현재 항목 삭제 후 Cancelled 반환 (이미 지운 항목은 복구되지 않음)
+    pub async fn remove_all<F>(
+        &self,
+        path: &str,
+        on_progress: F,
+        cancel: CancellationToken,
+    ) -> Result<RemoveResult>
+    where
+        F: Fn(&RemoveProgress),
+    {
+        let meta = self.sftp.symlink_metadata(path).await
+            .map_err(|e| Error::sftp(path, e))?;
+
+        let mut progress = RemoveProgress { files: 0, dirs: 0, path: String::new() };
+        if !FileType::of(&meta).is_dir() {
+            self.rm(path).await?;
+            progress.files += 1;
+            progress.path = path.to_string();
+            on_progress(&progress);
+            return Ok(RemoveResult::Completed { files: 1, dirs: 0 });
+        }
+
+        // (디렉토리, 하위 항목 처리 완료 여부) — 완료된 디렉토리를 다시 꺼내면 rmdir
+        let root = if path.len() > 1 { path.trim_end_matches('/') } else { path };
+        let mut stack = vec![(root.to_string(), false)];
+        while let Some((dir, emptied)) = stack.pop() {
+            if cancel.is_cancelled() {
+                return Ok(RemoveResult::Cancelled { files: progress.files, dirs: progress.dirs });
+            }
+            if emptied {
+                self.rmdir(&dir).await?;
+                progress.dirs += 1;
+                progress.path = dir;
+                on_progress(&progress);
+                continue;
+            }
+
+            let entries = self.sftp.read_dir(dir.as_str()).await
+                .map_err(|e| Error::sftp(&dir, e))?;
+            stack.push((dir.clone(), true));
+            for entry in entries {
+                let name = entry.file_name();
+                if name == "." || name == ".." {
+                    continue;
+                }
+                let child = format!("{}/{}", dir, name);
+                if FileType::of(&entry.metadata()).is_dir() {
+                    stack.push((child, false));
+                    continue;
+                }
+                if cancel.is_cancelled() {
+                    return Ok(RemoveResult::Cancelled { files: progress.files, dirs: progress.dirs });
+                }
+                self.rm(&child).await?;
+                progress.files += 1;
+                progress.path = child;
+                on_progress(&progress);
+            }
+        }
+        Ok(RemoveResult::Completed { files: progress.files, dirs: progress.dirs })
+    }
+
+    /// 권한 변경 (SSH_FXP_SETSTAT) → 적용된 권한 반환
+    ///
+    /// 기호 모드는 현재 권한을 먼저 읽음, 심볼릭 링크는 서버가 대상 파일에 적용
+    pub async fn chmod(&self, path: &str, mode: &ModeSpec) -> Result<u32> {
+        let mode = match mode {
+            ModeSpec::Octal(mode) => mode & 0o7777,
+            ModeSpec::Symbolic(_) => {
+                let current = self.sftp.metadata(path).await
+                    .map_err(|e| Error::sftp(path, e))?;
+                mode.apply(reported(current.permissions, path, "permissions")?, FileType::of(&current).is_dir())
+            }
+        };
+        self.setstat(path, FileAttributes { permissions: Some(mode), ..FileAttributes::empty() }).await?;
+        Ok(mode)
+    }
+
+    /// 소유자 / 그룹 변경 (std::os::unix::fs::chown과 같은 형태, 이름도 가능)
+    ///
+    /// SFTP v3는 uid와 gid를 함께 보내므로 None인 쪽은 현재 값을 읽어서 채움
+    /// (서버가 현재 값을 알려주지 않으면 에러, 0으로 대신하지 않음)
+    /// 이름은 resolve_owner()로 id를 찾고, 찾지 못하면 에러 (숫자 id로 지정)
+    pub async fn chown(&self, path: &str, user: Option<&Owner>, group: Option<&Owner>) -> Result<()> {
+        let (uid, gid) = self.resolve_owner(path, user, group).await?;
+        let (uid, gid) = match (uid, gid) {
+            (Some(uid), Some(gid)) => (uid, gid),
+            (None, None) => return Ok(()),
+            _ => {
+                let current = self.sftp.metadata(path).await
+                    .map_err(|e| Error::sftp(path, e))?;
+                (
+                    uid.map_or_else(|| reported(current.uid, path, "owner"), Ok)?,
+                    gid.map_or_else(|| reported(current.gid, path, "group"), Ok)?,
+                )
+            }
+        };
+        self.setstat(path, FileAttributes { uid: Some(uid), gid: Some(gid), ..FileAttributes::empty() }).await
+    }
+
+    /// 접근 / 수정 시각 변경 (UNIX 초)
+    ///
+    /// SFTP v3는 atime과 mtime을 함께 보내므로 None인 쪽은 현재 값 유지
+    /// (서버가 현재 값을 알려주지 않으면 에러, 0으로 대신하지 않음)
+    pub async fn set_times(&self, path: &str, atime: Option<u64>, mtime: Option<u64>) -> Result<()> {
+        let (atime, mtime) = match (atime, mtime) {
+            (Some(atime), Some(mtime)) => (atime, mtime),
+            (None, None) => return Ok(()),
+            _ => {
+                let current = self.sftp.metadata(path).await
+                    .map_err(|e| Error::sftp(path, e))?;
+                (
+                    atime.map_or_else(|| reported(current.atime.map(u64::from), path, "access time"), Ok)?,
+                    mtime.map_or_else(|| reported(current.mtime.map(u64::from), path, "modification time"), Ok)?,
+                )
+            }
+        };
+        self.setstat(path, FileAttributes {
+            atime: Some(sftp_time(atime, path)?),
+            mtime: Some(sftp_time(mtime, path)?),
+            ..FileAttributes::empty()
+        }).await
+    }
+
+    /// 파일이 없으면 빈 파일 생성, 있으면 내용은 그대로 두고 시각만 현재로
+    pub async fn touch(&self, path: &str) -> Result<()> {
+        let file = self.sftp.open_with_flags(path, OpenFlags::CREATE | OpenFlags::WRITE).await
+            .map_err(|e| Error::sftp(path, e))?;
+        drop(file);
+        let now = unix_now();
+        self.set_times(path, Some(now), Some(now)).await
+    }
+
+    /// set_times -R (path 포함 트리 전체, 심볼릭 링크는 건너뜀)
+    ///
+    /// None인 쪽은 항목마다 자기 현재 값 유지
+    pub async fn set_times_recursive(
+        &self,
+        path: &str,
+        atime: Option<u64>,
+        mtime: Option<u64>,
+        cancel: CancellationToken,
+    ) -> Result<WalkResult> {
+        let mut changed = 0;
+        if atime.is_none() && mtime.is_none() {
+            return Ok(WalkResult::Completed(changed));
+        }
+        for (entry, attrs) in self.tree(path).await? {
+            if cancel.is_cancelled() {
+                return Ok(WalkResult::Cancelled(changed));
+            }
+            if FileType::of(&attrs).is_symlink() {
+                continue;
+            }
+            let atime = atime.map_or_else(|| reported(attrs.atime.map(u64::from), &entry, "access time"), Ok)?;
+            let mtime = mtime.map_or_else(|| reported(attrs.mtime.map(u64::from), &entry, "modification time"), Ok)?;
+            self.setstat(&entry, FileAttributes {
+                atime: Some(sftp_time(atime, &entry)?),
+                mtime: Some(sftp_time(mtime, &entry)?),
+                ..FileAttributes::empty()
+            }).await?;
+            changed += 1;
+        }
+        Ok(WalkResult::Completed(changed))
+    }
+
+    /// touch -R (이미 있는 트리의 시각을 모두 현재로, 새 파일은 만들지 않음)
+    pub async fn touch_recursive(&self, path: &str, cancel: CancellationToken) -> Result<WalkResult> {
+        let now = unix_now();
+        self.set_times_recursive(path, Some(now), Some(now), cancel).await
+    }
+
+    /// chmod -R (path 포함 트리 전체, 심볼릭 링크는 건너뜀)
+    ///
+    /// 기호 모드는 항목마다 자기 권한 기준으로 적용 (X는 디렉토리에만 x 추가 등)
+    pub async fn chmod_recursive(&self, path: &str, mode: &ModeSpec, cancel: CancellationToken) -> Result<WalkResult> {
+        let mut changed = 0;
+        for (entry, attrs) in self.tree(path).await? {
+            if cancel.is_cancelled() {
+                return Ok(WalkResult::Cancelled(changed));
+            }
+            if FileType::of(&attrs).is_symlink() {
+                continue;
+            }
+            let mode = mode.apply(reported(attrs.permissions, &entry, "permissions")?, FileType::of(&attrs).is_dir());
+            self.setstat(&entry, FileAttributes { permissions: Some(mode), ..FileAttributes::empty() }).await?;
+            changed += 1;
+        }
+        Ok(WalkResult::Completed(changed))
+    }
+
+    /// chown -R (path 포함 트리 전체, 심볼릭 링크는 건너뜀)
+    pub async fn chown_recursive(
+        &self,
+        path: &str,
+        user: Option<&Owner>,
+        group: Option<&Owner>,
+        cancel: CancellationToken,
+    ) -> Result<WalkResult> {
+        let (uid, gid) = self.resolve_owner(path, user, group).await?;
+        let mut changed = 0;
+        for (entry, attrs) in self.tree(path).await? {
+            if cancel.is_cancelled() {
+                return Ok(WalkResult::Cancelled(changed));
+            }
+            if FileType::of(&attrs).is_symlink() {
+                continue;
+            }
+            let uid = uid.map_or_else(|| reported(attrs.uid, &entry, "owner"), Ok)?;
+            let gid = gid.map_or_else(|| reported(attrs.gid, &entry, "group"), Ok)?;
+            self.setstat(&entry, FileAttributes { uid: Some(uid), gid: Some(gid), ..FileAttributes::empty() }).await?;
+            changed += 1;
+        }
+        Ok(WalkResult::Completed(changed))
+    }
+
+    /// Owner → 숫자 id (이름이 없으면 서버에 묻지 않음)
+    ///
+    /// SFTP v3에는 이름 → id 조회가 없으므로 path의 상위 디렉토리, path(디렉토리면), 홈 디렉토리
+    /// 항목의 uid / gid와 소유자 이름을 짝지어 찾음
+    /// - 서버가 users-groups-by-id@openssh.com을 지원하면 그 응답의 이름 (0~1999, 65534도 함께 물어봄)
+    /// - 아니면 READDIR longname("-rw-r--r-- 1 owner group ...")의 이름
+    ///
+    /// 이름을 찾지 못하면 에러 (확장이 없으면 주변 항목의 소유자 / 그룹 이름만 찾을 수 있음)
+    pub async fn resolve_owner(
+        &self,
+        path: &str,
+        user: Option<&Owner>,
+        group: Option<&Owner>,
+    ) -> Result<(Option<u32>, Option<u32>)> {
+        let by_name = |o: Option<&Owner>| matches!(o, Some(Owner::Name(_)));
+        let names = if by_name(user) || by_name(group) {
+            self.owner_names(path).await?
+        } else {
+            OwnerNames::default()
+        };
+
+        let resolve = |owner: Option<&Owner>, map: &HashMap<String, u32>, what: &str| match owner {
+            None                  => Ok(None),
+            Some(Owner::Id(id))   => Ok(Some(*id)),
+            Some(Owner::Name(name)) => map.get(name).copied().map(Some).ok_or_else(|| Error::Sftp {
+                kind: SftpErrorKind::Other,
+                path: Some(path.to_string()),
+                message: format!(
+                    "cannot map {} name '{}' to an id: the server does not report it (use a numeric id)",
+                    what, name,
+                ),
+                source: None,
+            }),
+        };
+        Ok((resolve(user, &names.users, "user")?, resolve(group, &names.groups, "group")?))
+    }
+
+    /// path 주변 디렉토리 항목의 이름 → id (resolve_owner 참고)
+    async fn owner_names(&self, path: &str) -> Result<OwnerNames> {
+        let raw = self.raw.as_deref().ok_or_else(|| Error::Sftp {
+            kind: SftpErrorKind::OpUnsupported,
+            path: Some(path.to_string()),
+            message: "cannot look up owner names: no raw SFTP channel (use a numeric id)".to_string(),
+            source: None,
+        })?;
+
+        let mut dirs = vec![parent_dir(path).to_string(), path.to_string(), ".".to_string()];
+        dirs.dedup();
+        // (uid, gid, longname의 소유자, longname의 그룹)
+        let mut owners: Vec<(u32, u32, String, String)> = Vec::new();
+        for dir in &dirs {
+            match read_dir_long(&raw.session, dir).await {
+                Ok(files) => owners.extend(files.into_iter().filter_map(|f| {
+                    let mut cols = f.longname.split_whitespace().skip(2);
+                    let (user, group) = (cols.next()?.to_string(), cols.next()?.to_string());
+                    Some((f.attrs.uid?, f.attrs.gid?, user, group))
+                })),
+                // 디렉토리가 아니거나 읽을 수 없으면 다음 후보로
+                Err(e) => tracing::debug!("[sftp] owner lookup: cannot list {}: {}", dir, e),
+            }
+        }
+
+        let mut names = OwnerNames::default();
+        if raw.supports(USERS_GROUPS_BY_ID) {
+            let ids = |nearby: Vec<u32>| {
+                let mut ids: Vec<u32> = nearby.into_iter().chain(LOOKUP_IDS).chain([NOBODY_ID]).collect();
+                ids.sort_unstable();
+                ids.dedup();
+                ids
+            };
+            let uids = ids(owners.iter().map(|o| o.0).collect());
+            let gids = ids(owners.iter().map(|o| o.1).collect());
+            let (users, groups) = users_groups_by_id(&raw.session, &uids, &gids).await
+                .map_err(|e| Error::sftp(path, e))?;
+            names.users.extend(users.into_iter().zip(uids).filter(|(name, _)| !name.is_empty()));
+            names.groups.extend(groups.into_iter().zip(gids).filter(|(name, _)| !name.is_empty()));
+            return Ok(names);
+        }
+        // longname의 이름 (서버가 이름을 모르면 숫자 그대로 → id와 같으면 이름이 아님)
+        for (uid, gid, user, group) in owners {
+            if user != uid.to_string() {
+                names.users.entry(user).or_insert(uid);
+            }
+            if group != gid.to_string() {
+                names.groups.entry(group).or_insert(gid);
+            }
+        }
+        Ok(names)
+    }
+
+    async fn setstat(&self, path: &str, attrs: FileAttributes) -> Result<()> {
+        self.sftp.set_metadata(path, attrs).await
+            .map_err(|e| Error::sftp(path, e))
+    }
+
+    /// path와 그 아래 모든 항목 (위에서부터, 심볼릭 링크 디렉토리는 들어가지 않음)
+    async fn tree(&self, path: &str) -> Result<Vec<(String, FileAttributes)>> {
+        let root_attrs = self.sftp.symlink_metadata(path).await
+            .map_err(|e| Error::sftp(path, e))?;
+        let root = if path.len() > 1 { path.trim_end_matches('/') } else { path };
+        let mut pending = if FileType::of(&root_attrs).is_dir() { vec![root.to_string()] } else { Vec::new() };
+        let mut entries = vec![(root.to_string(), root_attrs)];
+
+        while let Some(dir) = pending.pop() {
+            let list = self.sftp.read_dir(dir.as_str()).await
+                .map_err(|e| Error::sftp(&dir, e))?;
+            for entry in list {
+                let name = entry.file_name();
+                if name == "." || name == ".." {
+                    continue;
+                }
+                let child = format!("{}/{}", dir, name);
+                let attrs = entry.metadata();
+                if FileType::of(&attrs).is_dir() {
+                    pending.push(child.clone());
+                }
+                entries.push((child, attrs));
+            }
+        }
+        Ok(entries)
+    }
+
     /// 서버에 경로의 절대경로 확인 (SSH_FXP_REALPATH)
     ///
     /// 용도 A: 접속 직후 홈 디렉토리 확인  → sftp.realpath(".")
     /// 용도 B: cd 후 심볼릭 링크 해소      → sftp.realpath(&current_dir)
-    pub async fn realpath(&mut self, path: &str) -> Result<String> {
+    pub async fn realpath(&self, path: &str) -> Result<String> {
         self.sftp.canonicalize(path).await
-            .map_err(|e| Error::Protocol(e.to_string()))
+            .map_err(|e| Error::sftp(path, e))
     }
 
     /// 현재 작업 디렉토리를 서버에 직접 확인 (방식 B)
-    pub async fn pwd(&mut self, current_dir: &str) -> Result<String> {
+    pub async fn pwd(&self, current_dir: &str) -> Result<String> {
         self.realpath(current_dir).await
     }
 }
+
+/// 현재 시각 (UNIX 초)
+fn unix_now() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+/// UNIX 초 → SFTP v3 시각 (uint32, 2106-02-07 이후는 보낼 수 없으므로 에러)
+fn sftp_time(secs: u64, path: &str) -> Result<u32> {
+    u32::try_from(secs).map_err(|_| Error::Sftp {
+        kind: SftpErrorKind::Other,
+        path: Some(path.to_string()),
+        message: format!("time {} is out of range for SFTP v3 (32-bit seconds)", secs),
+        source: None,
+    })
+}
+
+/// 로컬 파일 시각 → SFTP v3 시각 (범위를 넘으면 LocalIo)
+fn local_time(secs: u64, local: &str) -> Result<u32> {
+    u32::try_from(secs).map_err(|_| Error::local_io(local, std::io::Error::new(
+        std::io::ErrorKind::InvalidData,
+        format!("time {} is out of range for SFTP v3 (32-bit seconds)", secs),
+    )))
+}
+
+/// 서버가 알려준 현재 속성 (없으면 에러)
+fn reported<T>(value: Option<T>, path: &str, what: &str) -> Result<T> {
+    value.ok_or_else(|| Error::Sftp {
+        kind: SftpErrorKind::Other,
+        path: Some(path.to_string()),
+        message: format!("server did not report the current {}", what),
+        source: None,
+    })
+}
+
+/// 서버에서 찾은 사용자 / 그룹 이름 → id
+#[derive(Default)]
+struct OwnerNames {
+    users: HashMap<String, u32>,
+    groups: HashMap<String, u32>,
+}
+
+/// 상위 디렉토리 ("/a/b" → "/a", "/a" → "/", "b" → ".")
+fn parent_dir(path: &str) -> &str {
+    let trimmed = if path.len() > 1 { path.trim_end_matches('/') } else { path };
+    match trimmed.rfind('/') {
+        Some(0) => "/",
+        Some(i) => &trimmed[..i],
+        None    => ".",
+    }
+}
+
+/// 경로의 마지막 구성요소 ("/a/b/c" → "c", "/" → "/")
+fn file_name(path: &str) -> &str {
+    path.trim_end_matches('/').rsplit('/').next().filter(|n| !n.is_empty()).unwrap_or(path)
+}
+
+/// posix-rename@openssh.com 확장 요청 (data: string oldpath, string newpath → SSH_FXP_STATUS)
+async fn posix_rename(ext: &RawSftpSession, from: &str, to: &str) -> std::result::Result<(), RusshSftpError> {
+    let mut data = Vec::with_capacity(8 + from.len() + to.len());
+    for path in [from, to] {
+        data.extend_from_slice(&(path.len() as u32).to_be_bytes());
+        data.extend_from_slice(path.as_bytes());
+    }
+    match ext.extended(POSIX_RENAME, data).await? {
+        Packet::Status(status) if status.status_code == StatusCode::Ok => Ok(()),
+        Packet::Status(status) => Err(status.into()),
+        _ => Err(RusshSftpError::UnexpectedPacket),
+    }
+}
+
+/// longname을 포함한 디렉토리 목록 (SSH_FXP_OPENDIR / READDIR / CLOSE)
+async fn read_dir_long(raw: &RawSftpSession, dir: &str) -> std::result::Result<Vec<russh_sftp::protocol::File>, RusshSftpError> {
+    let handle = raw.opendir(dir).await?.handle;
+    let mut files = Vec::new();
+    let result = loop {
+        match raw.readdir(handle.as_str()).await {
+            Ok(name) => files.extend(name.files),
+            Err(RusshSftpError::Status(status)) if status.status_code == StatusCode::Eof => break Ok(files),
+            Err(e) => break Err(e),
+        }
+    };
+    let _ = raw.close(handle).await;
+    result
+}
+
+/// users-groups-by-id@openssh.com 확장 요청
+///
+/// data: string uids(uint32 나열), string gids → SSH_FXP_EXTENDED_REPLY: string 이름들, string 이름들
+/// 이름은 요청한 순서대로, 서버가 모르는 id는 빈 문자열
+async fn users_groups_by_id(
+    raw: &RawSftpSession,
+    uids: &[u32],
+    gids: &[u32],
+) -> std::result::Result<(Vec<String>, Vec<String>), RusshSftpError> {
+    let mut data = Vec::with_capacity(8 + 4 * (uids.len() + gids.len()));
+    for ids in [uids, gids] {
+        data.extend_from_slice(&((ids.len() * 4) as u32).to_be_bytes());
+        for id in ids {
+            data.extend_from_slice(&id.to_be_bytes());
+        }
+    }
+    let reply = match raw.extended(USERS_GROUPS_BY_ID, data).await? {
+        Packet::ExtendedReply(reply) => reply.data,
+        Packet::Status(status) => return Err(status.into()),
+        _ => return Err(RusshSftpError::UnexpectedPacket),
+    };
+
+    let bad = || RusshSftpError::UnexpectedBehavior(format!("malformed {} reply", USERS_GROUPS_BY_ID));
+    let mut pos = 0;
+    let users = read_ssh_string(&reply, &mut pos).ok_or_else(bad)?;
+    let groups = read_ssh_string(&reply, &mut pos).ok_or_else(bad)?;
+    let names = |block: &[u8], count: usize| -> Option<Vec<String>> {
+        let mut pos = 0;
+        let names = (0..count)
+            .map(|_| read_ssh_string(block, &mut pos).map(|n| String::from_utf8_lossy(n).to_string()))
+            .collect::<Option<Vec<_>>>()?;
+        (pos == block.len()).then_some(names)
+    };
+    Ok((names(users, uids.len()).ok_or_else(bad)?, names(groups, gids.len()).ok_or_else(bad)?))
+}
+
+/// SSH string (uint32 길이 + 바이트) 하나, 길이가 남은 데이터보다 크면 None
+fn read_ssh_string<'a>(buf: &'a [u8], pos: &mut usize) -> Option<&'a [u8]> {
+    let len_end = pos.checked_add(4)?;
+    let len = u32::from_be_bytes(buf.get(*pos..len_end)?.try_into().ok()?) as usize;
+    let end = len_end.checked_add(len)?;
+    let out = buf.get(len_end..end)?;
+    *pos = end;
+    Some(out)
+}
+
+/// 받은 로컬 파일에 리모트 atime / mtime (preserve_mode면 권한도) 적용
+fn set_local_attrs(file: std::fs::File, local: &str, attrs: &FileAttributes, preserve_mode: bool) -> Result<()> {
+    if let Some(mtime) = attrs.mtime {
+        let at = |secs: u32| std::time::UNIX_EPOCH + std::time::Duration::from_secs(secs as u64);
+        let times = std::fs::FileTimes::new()
+            .set_accessed(at(attrs.atime.unwrap_or(mtime)))
+            .set_modified(at(mtime));
+        file.set_times(times).map_err(|e| Error::local_io(local, e))?;
+    }
+    #[cfg(unix)]
+    if let Some(mode) = attrs.permissions.filter(|_| preserve_mode) {
+        use std::os::unix::fs::PermissionsExt;
+        file.set_permissions(std::fs::Permissions::from_mode(mode & 0o7777))
+            .map_err(|e| Error::local_io(local, e))?;
+    }
+    #[cfg(not(unix))]
+    let _ = preserve_mode;
+    Ok(())
+}
+
+/// 로컬 파일 권한 비트 (unix 외에는 None)
+fn local_mode(meta: &std::fs::Metadata) -> Option<u32> {
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        Some(meta.permissions().mode() & 0o7777)
+    }
+    #[cfg(not(unix))]
+    {
+        let _ = meta;
+        None
+    }
+}
+
+/// 로컬 심볼릭 링크 생성 (이미 같은 링크면 그대로, 다른 파일 / 링크는 교체, 디렉토리는 에러)
+#[cfg(unix)]
+fn local_symlink(target: &str, local: &str) -> Result<()> {
+    if let Ok(meta) = std::fs::symlink_metadata(local) {
+        if meta.is_symlink() && std::fs::read_link(local).is_ok_and(|t| t.as_os_str() == target) {
+            return Ok(());
+        }
+        std::fs::remove_file(local).map_err(|e| Error::local_io(local, e))?;
+    }
+    std::os::unix::fs::symlink(target, local).map_err(|e| Error::local_io(local, e))
+}
+
+#[cfg(not(unix))]
+fn local_symlink(_target: &str, local: &str) -> Result<()> {
+    Err(Error::local_io(local, std::io::Error::new(
+        std::io::ErrorKind::Unsupported,
+        "symlinks can only be preserved on unix",
+    )))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn mode(s: &str) -> ModeSpec {
+        s.parse().unwrap_or_else(|e| panic!("{}: {}", s, e))
+    }
+
+    #[test]
+    fn mode_octal() {
+        assert_eq!(mode("755"), ModeSpec::Octal(0o755));
+        assert_eq!(mode("0644").apply(0o100755, false), 0o644);
+        // 타입 비트는 버리고 특수 비트는 유지
+        assert_eq!(mode("4755").apply(0o100644, false), 0o4755);
+        assert!("758".parse::<ModeSpec>().is_err());
+        assert!("75555".parse::<ModeSpec>().is_err());
+    }
+
+    #[test]
+    fn mode_symbolic() {
+        assert_eq!(mode("g+w").apply(0o100644, false), 0o664);
+        assert_eq!(mode("u=rx,o-r").apply(0o644, false), 0o540);
+        assert_eq!(mode("go=u").apply(0o750, false), 0o777);
+        assert_eq!(mode("+x").apply(0o644, false), 0o755);
+        assert_eq!(mode("u+x-w").apply(0o644, false), 0o544);
+        assert_eq!(mode("u+s,o+t").apply(0o755, true), 0o5755);
+    }
+
+    #[test]
+    fn mode_capital_x() {
+        let spec = mode("a+X");
+        // 디렉토리이거나 이미 누군가 실행 권한이 있을 때만 x
+        assert_eq!(spec.apply(0o644, false), 0o644);
+        assert_eq!(spec.apply(0o744, false), 0o755);
+        assert_eq!(spec.apply(0o040700, true), 0o711);
+    }
+
+    #[test]
+    fn mode_invalid() {
+        for s in ["", "u", "u+q", "z+x", "u+x,", "u=gx"] {
+            assert!(s.parse::<ModeSpec>().is_err(), "{}", s);
+        }
+    }
+
+    #[test]
+    fn owner_parse() {
+        assert_eq!("1000".parse::<Owner>().unwrap(), Owner::Id(1000));
+        assert_eq!("www-data".parse::<Owner>().unwrap(), Owner::Name("www-data".to_string()));
+        assert!("".parse::<Owner>().is_err());
+        assert!("99999999999".parse::<Owner>().is_err());
+    }
+
+    #[test]
+    fn parent_dir_of() {
+        assert_eq!(parent_dir("/a/b"), "/a");
+        assert_eq!(parent_dir("/a/b/"), "/a");
+        assert_eq!(parent_dir("/a"), "/");
+        assert_eq!(parent_dir("b"), ".");
+        assert_eq!(parent_dir("/"), "/");
+    }
+}
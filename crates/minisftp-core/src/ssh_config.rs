@@ -0,0 +1,629 @@
+// miniSFTP OpenSSH Client Config (~/.ssh/config)
+// author: kodeholic (powered by Claude)
+//
+// Host 별칭 → ConnectConfig 변환
+//
+// OpenSSH 규칙
+// - 키워드는 대소문자 무시, "Keyword value" 또는 "Keyword=value"
+// - 첫 번째로 얻은 값이 우선 (first-match-wins), IdentityFile만 누적
+// - Host 패턴: 공백 구분, *, ?, !부정 (명령줄에 입력한 별칭과 비교)
+// - Include: ~/.ssh 기준 상대경로, 파일명 와일드카드 허용
+//   Host 블록 안의 Include는 해당 블록이 일치할 때만 적용
+// - ProxyJump: 각 hop을 같은 설정 파일의 별칭으로 해석 → ConnectConfig::jump_hosts
+// - Match 블록은 미지원 → 블록 전체를 건너뜀
+//
+// 지원 키워드: HostName, Port, User, IdentityFile, IdentitiesOnly, ProxyJump, ProxyCommand,
+//             StrictHostKeyChecking, UserKnownHostsFile, ConnectTimeout, ServerAliveInterval, ServerAliveCountMax,
+//             KexAlgorithms, HostKeyAlgorithms, Ciphers, MACs, Compression
+
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use crate::algorithms::{self, AlgorithmKind};
+use crate::config::{
+    Algorithms, AuthMethod, ConnectConfig, HostKeyPolicy, JumpHost, Keepalive, ProxyConfig, ReconnectPolicy, Timeouts,
+};
+use crate::error::{Error, Result};
+use crate::utils::wildcard_match;
+
+const MAX_INCLUDE_DEPTH: usize = 16;
+
+/// 별칭 하나에 대해 해석된 설정 (지정되지 않은 항목은 None)
+#[derive(Debug, Clone, Default)]
+pub struct HostConfig {
+    pub host_name: Option<String>,
+    pub port: Option<u16>,
+    pub user: Option<String>,
+    pub identity_files: Vec<PathBuf>,
+    pub identities_only: Option<bool>,
+    /// "user@jump1:22,jump2" 형식 그대로 (none이면 None)
+    pub proxy_jump: Option<String>,
+    /// 명령 문자열 그대로 (토큰은 연결 시 치환, none이면 None)
+    /// ProxyJump와 ProxyCommand는 먼저 나온 쪽만 적용 (OpenSSH 동작)
+    pub proxy_command: Option<String>,
+    /// proxy_jump를 hop별로 해석한 결과 (각 hop도 같은 설정 파일의 별칭으로 해석)
+    pub jump_hosts: Vec<JumpHost>,
+    pub strict_host_key_checking: Option<HostKeyPolicy>,
+    pub user_known_hosts_file: Option<PathBuf>,
+    /// ConnectTimeout (초)
+    pub connect_timeout: Option<u64>,
+    /// ServerAliveInterval (초, 0이면 keepalive 끔)
+    pub server_alive_interval: Option<u64>,
+    pub server_alive_count_max: Option<usize>,
+    /// 알고리즘 목록 (OpenSSH 표기 그대로: "a,b" / "+a" / "-a*" / "^a")
+    pub kex_algorithms: Option<String>,
+    pub host_key_algorithms: Option<String>,
+    pub ciphers: Option<String>,
+    pub macs: Option<String>,
+    /// Compression yes → zlib@openssh.com, zlib 우선
+    pub compression: Option<bool>,
+}
+
+/// 기본 설정 파일 경로 (~/.ssh/config)
+pub fn default_path() -> Option<PathBuf> {
+    home::home_dir().map(|h| h.join(".ssh").join("config"))
+}
+
+/// ~/.ssh/config에서 별칭 해석 (파일이 없으면 빈 설정)
+pub fn resolve_default(alias: &str) -> Result<HostConfig> {
+    match default_path() {
+        Some(path) => resolve(&path, alias),
+        None => Ok(HostConfig::default()),
+    }
+}
+
+/// 설정 파일에서 별칭 해석 (ProxyJump → jump_hosts 포함)
+pub fn resolve(path: &Path, alias: &str) -> Result<HostConfig> {
+    let mut resolved = resolve_host(path, alias)?;
+    if let Some(spec) = &resolved.proxy_jump {
+        resolved.jump_hosts = parse_proxy_jump(path, spec)?;
+    }
+    Ok(resolved)
+}
+
+/// ProxyJump 문자열 해석: "[user@]host[:port],..." (ssh:// 접두사, [IPv6]:port 허용)
+///
+/// 각 hop의 host는 설정 파일의 별칭으로 해석 (HostName, Port, User, IdentityFile 적용)
+/// 명시한 user / port가 설정보다 우선, hop 자신의 ProxyJump는 따르지 않음
+/// user를 정할 수 없으면 (명시 / User / 로컬 사용자명 모두 없음) Error::Config
+pub fn parse_proxy_jump(path: &Path, spec: &str) -> Result<Vec<JumpHost>> {
+    let mut hops = Vec::new();
+    for item in spec.split(',').map(str::trim).filter(|s| !s.is_empty()) {
+        let item = item.strip_prefix("ssh://").unwrap_or(item);
+        let (user, host_port) = match item.rsplit_once('@') {
+            Some((u, h)) => (Some(u), h),
+            None => (None, item),
+        };
+        let (host, port) = split_host_port(host_port);
+
+        let mut resolved = resolve_host(path, host)?;
+        if let Some(user) = user {
+            resolved.user = Some(user.to_string());
+        }
+        let mut hop = resolved.to_connect_config(host)?;
+        if let Some(port) = port {
+            hop.port = port;
+        }
+        hops.push(JumpHost {
+            host: hop.host,
+            port: hop.port,
+            username: hop.username,
+            auth_methods: hop.auth_methods,
+        });
+    }
+    Ok(hops)
+}
+
+/// "host", "host:port", "[v6]:port" 분리
+fn split_host_port(s: &str) -> (&str, Option<u16>) {
+    if let Some(rest) = s.strip_prefix('[') {
+        if let Some((host, tail)) = rest.split_once(']') {
+            return (host, tail.strip_prefix(':').and_then(|p| p.parse().ok()));
+        }
+    }
+    match s.rsplit_once(':') {
+        Some((host, port)) if !host.contains(':') => (host, port.parse().ok()),
+        _ => (s, None),
+    }
+}
+
+/// ProxyJump를 제외한 별칭 해석
+fn resolve_host(path: &Path, alias: &str) -> Result<HostConfig> {
+    let mut resolved = HostConfig::default();
+    let mut resolver = Resolver {
+        alias: alias.to_lowercase(),
+        base_dir: path.parent().map(Path::to_path_buf).unwrap_or_default(),
+        config: &mut resolved,
+        proxy_seen: false,
+    };
+    if path.exists() {
+        resolver.read_file(path, 0)?;
+    }
+
+    // 토큰 / ~ 확장 (모든 값이 확정된 뒤 수행: %h는 HostName, %r은 User 참조)
+    let host = match &resolved.host_name {
+        Some(h) => expand_tokens(h, alias, alias, None, resolved.user.as_deref())?,
+        None => alias.to_string(),
+    };
+    let expand_path = |p: &PathBuf| {
+        expand_tokens(&p.to_string_lossy(), alias, &host, resolved.port, resolved.user.as_deref()).map(PathBuf::from)
+    };
+    resolved.identity_files = resolved.identity_files.iter().map(expand_path).collect::<Result<_>>()?;
+    resolved.user_known_hosts_file = resolved.user_known_hosts_file.as_ref().map(expand_path).transpose()?;
+    resolved.host_name = Some(host);
+
+    Ok(resolved)
+}
+
+impl HostConfig {
+    /// ConnectConfig로 변환
+    ///
+    /// - User 미지정 → 로컬 사용자명 ($USER / %USERNAME%), 그것도 없으면 Error::Config
+    ///   (명령줄 user@host처럼 호출측 사용자명은 변환 전에 user에 넣음)
+    /// - 인증 순서: ssh-agent(SSH_AUTH_SOCK이 있고 IdentitiesOnly가 아니면) → IdentityFile
+    ///   IdentityFile 미지정 시 OpenSSH 기본 키(~/.ssh/id_ed25519, id_ecdsa, id_rsa) 중 존재하는 파일
+    /// - 패스워드는 포함하지 않음 (필요하면 호출측에서 auth_methods에 추가)
+    pub fn to_connect_config(&self, alias: &str) -> Result<ConnectConfig> {
+        let mut auth_methods = Vec::new();
+        if std::env::var_os("SSH_AUTH_SOCK").is_some() && self.identities_only != Some(true) {
+            auth_methods.push(AuthMethod::Agent { identity: None });
+        }
+
+        let identity_files = if self.identity_files.is_empty() {
+            default_identity_files()
+        } else {
+            self.identity_files.clone()
+        };
+        for path in identity_files {
+            auth_methods.push(AuthMethod::PublicKey { private_key_path: path, passphrase: None });
+        }
+
+        Ok(ConnectConfig {
+            host: self.host_name.clone().unwrap_or_else(|| alias.to_string()),
+            port: self.port.unwrap_or(22),
+            username: match &self.user {
+                Some(user) => user.clone(),
+                None => local_username()?,
+            },
+            auth_methods,
+            host_key_policy: self.strict_host_key_checking.clone().unwrap_or_default(),
+            known_hosts_path: self.user_known_hosts_file.clone(),
+            jump_hosts: self.jump_hosts.clone(),
+            proxy: self.proxy_command.clone().map(ProxyConfig::Command),
+            reconnect: ReconnectPolicy::default(),
+            timeouts: self.timeouts(),
+            keepalive: self.keepalive(),
+            algorithms: self.algorithms(),
+        })
+    }
+
+    /// KexAlgorithms / HostKeyAlgorithms / Ciphers / MACs / Compression → 알고리즘 우선순위
+    fn algorithms(&self) -> Algorithms {
+        let spec = |kind, value: &Option<String>| {
+            value.as_deref().map(|v| algorithms::parse_spec(kind, v)).unwrap_or_default()
+        };
+        Algorithms {
+            kex: spec(AlgorithmKind::Kex, &self.kex_algorithms),
+            host_key: spec(AlgorithmKind::HostKey, &self.host_key_algorithms),
+            cipher: spec(AlgorithmKind::Cipher, &self.ciphers),
+            mac: spec(AlgorithmKind::Mac, &self.macs),
+            compression: match self.compression {
+                Some(true) => vec!["zlib@openssh.com".into(), "zlib".into(), "none".into()],
+                _ => Vec::new(),
+            },
+        }
+    }
+
+    /// ConnectTimeout → TCP 연결 타임아웃 (나머지 단계는 기본값)
+    fn timeouts(&self) -> Timeouts {
+        let mut timeouts = Timeouts::default();
+        if let Some(secs) = self.connect_timeout {
+            timeouts.tcp_connect = Some(Duration::from_secs(secs));
+        }
+        timeouts
+    }
+
+    /// ServerAliveInterval / ServerAliveCountMax → keepalive
+    fn keepalive(&self) -> Keepalive {
+        let mut keepalive = Keepalive::default();
+        match self.server_alive_interval {
+            Some(0) => keepalive.interval = None,
+            Some(secs) => keepalive.interval = Some(Duration::from_secs(secs)),
+            None => {}
+        }
+        if let Some(max) = self.server_alive_count_max {
+            keepalive.max_missed = max;
+        }
+        keepalive
+    }
+}
+
+// ── 해석 ─────────────────────────────────────────────────────────────────────
+
+struct Resolver<'a> {
+    alias: String,
+    base_dir: PathBuf,
+    config: &'a mut HostConfig,
+    // ProxyJump / ProxyCommand 중 먼저 나온 값만 사용 (none 포함)
+    proxy_seen: bool,
+}
+
+impl Resolver<'_> {
+    /// 파일 하나 처리 (Host 블록 활성 여부는 파일 단위로 시작: 블록 밖 = 전역)
+    fn read_file(&mut self, path: &Path, depth: usize) -> Result<()> {
+        if depth > MAX_INCLUDE_DEPTH {
+            tracing::warn!("[ssh_config] Include nested too deeply: {}", path.display());
+            return Ok(());
+        }
+        let content = std::fs::read_to_string(path)?;
+
+        let mut active = true;
+        for (idx, line) in content.lines().enumerate() {
+            let Some((keyword, args)) = split_line(line) else { continue };
+
+            // ProxyCommand는 셸 명령이므로 인자 분리 없이 줄 나머지 전체를 값으로 사용
+            if keyword == "proxycommand" {
+                if active {
+                    self.apply_proxy_command(raw_value(line));
+                }
+                continue;
+            }
+
+            match keyword.as_str() {
+                "host"  => active = self.host_matches(&args),
+                "match" => {
+                    tracing::debug!("[ssh_config] {}:{}: Match is not supported, skipping block", path.display(), idx + 1);
+                    active = false;
+                }
+                "include" if active => {
+                    for pattern in &args {
+                        for file in self.include_files(pattern) {
+                            self.read_file(&file, depth + 1)?;
+                        }
+                    }
+                }
+                _ if active => self.apply(&keyword, &args),
+                _ => {}
+            }
+        }
+        Ok(())
+    }
+
+    fn host_matches(&self, patterns: &[String]) -> bool {
+        let mut matched = false;
+        for pattern in patterns {
+            let pattern = pattern.to_lowercase();
+            match pattern.strip_prefix('!') {
+                Some(negated) if wildcard_match(negated, &self.alias) => return false,
+                Some(_) => {}
+                None if wildcard_match(&pattern, &self.alias) => matched = true,
+                None => {}
+            }
+        }
+        matched
+    }
+
+    /// 키워드 적용 (이미 값이 있으면 무시 = first-match-wins)
+    fn apply(&mut self, keyword: &str, args: &[String]) {
+        let Some(value) = args.first() else { return };
+        let c = &mut *self.config;
+        match keyword {
+            "hostname"  => { c.host_name.get_or_insert_with(|| value.clone()); }
+            "port" if c.port.is_none() => c.port = value.parse().ok(),
+            "user"      => { c.user.get_or_insert_with(|| value.clone()); }
+            "identityfile" => {
+                if value.eq_ignore_ascii_case("none") { return; }
+                c.identity_files.push(PathBuf::from(value));
+            }
+            "identitiesonly" => { c.identities_only.get_or_insert(value.eq_ignore_ascii_case("yes")); }
+            // none도 "첫 값"으로 취급 → 이후 블록의 ProxyJump / ProxyCommand 무시
+            "proxyjump" if !self.proxy_seen => {
+                self.proxy_seen = true;
+                if !value.eq_ignore_ascii_case("none") {
+                    c.proxy_jump = Some(value.clone());
+                }
+            }
+            "stricthostkeychecking" if c.strict_host_key_checking.is_none() => {
+                c.strict_host_key_checking = HostKeyPolicy::from_openssh(value);
+            }
+            "userknownhostsfile" => { c.user_known_hosts_file.get_or_insert_with(|| PathBuf::from(value)); }
+            "connecttimeout" if c.connect_timeout.is_none() => c.connect_timeout = value.parse().ok(),
+            "serveraliveinterval" if c.server_alive_interval.is_none() => c.server_alive_interval = value.parse().ok(),
+            "serveralivecountmax" if c.server_alive_count_max.is_none() => c.server_alive_count_max = value.parse().ok(),
+            "kexalgorithms"     => { c.kex_algorithms.get_or_insert_with(|| value.clone()); }
+            "hostkeyalgorithms" => { c.host_key_algorithms.get_or_insert_with(|| value.clone()); }
+            "ciphers"           => { c.ciphers.get_or_insert_with(|| value.clone()); }
+            "macs"              => { c.macs.get_or_insert_with(|| value.clone()); }
+            "compression"       => { c.compression.get_or_insert(value.eq_ignore_ascii_case("yes")); }
+            _ => {}
+        }
+    }
+
+    fn apply_proxy_command(&mut self, value: &str) {
+        if self.proxy_seen || value.is_empty() {
+            return;
+        }
+        self.proxy_seen = true;
+        if !value.eq_ignore_ascii_case("none") {
+            self.config.proxy_command = Some(value.to_string());
+        }
+    }
+
+    /// Include 대상 파일 목록 (상대경로는 ~/.ssh 기준, 파일명 와일드카드)
+    fn include_files(&self, pattern: &str) -> Vec<PathBuf> {
+        let expanded = expand_tilde(pattern);
+        let path = if Path::new(&expanded).is_absolute() {
+            PathBuf::from(expanded)
+        } else {
+            self.base_dir.join(expanded)
+        };
+
+        let file_pattern = path.file_name().map(|f| f.to_string_lossy().to_string()).unwrap_or_default();
+        if !file_pattern.contains(['*', '?']) {
+            return if path.is_file() { vec![path] } else { Vec::new() };
+        }
+
+        let dir = path.parent().unwrap_or(Path::new("."));
+        let mut files: Vec<PathBuf> = std::fs::read_dir(dir)
+            .map(|rd| rd.filter_map(|e| e.ok())
+                .filter(|e| wildcard_match(&file_pattern, &e.file_name().to_string_lossy()))
+                .map(|e| e.path())
+                .filter(|p| p.is_file())
+                .collect())
+            .unwrap_or_default();
+        files.sort();
+        files
+    }
+}
+
+// ── 파싱 / 확장 ──────────────────────────────────────────────────────────────
+
+/// 한 줄 → (소문자 키워드, 인자들). 빈 줄/주석이면 None
+///
+/// "Keyword value", "Keyword=value", "Keyword = value" 모두 허용, 큰따옴표 인자 지원
+fn split_line(line: &str) -> Option<(String, Vec<String>)> {
+    let line = line.trim();
+    if line.is_empty() || line.starts_with('#') {
+        return None;
+    }
+
+    let end = line.find(|c: char| c.is_whitespace() || c == '=').unwrap_or(line.len());
+    let keyword = line[..end].to_lowercase();
+    let rest = line[end..].trim_start();
+    let rest = rest.strip_prefix('=').unwrap_or(rest);
+
+    let mut args = Vec::new();
+    let mut current = String::new();
+    let mut in_quotes = false;
+    let mut has_token = false;
+    for ch in rest.chars() {
+        match ch {
+            '"' => { in_quotes = !in_quotes; has_token = true; }
+            c if c.is_whitespace() && !in_quotes => {
+                if has_token {
+                    args.push(std::mem::take(&mut current));
+                    has_token = false;
+                }
+            }
+            '#' if !in_quotes && !has_token => break,
+            c => { current.push(c); has_token = true; }
+        }
+    }
+    if has_token {
+        args.push(current);
+    }
+
+    Some((keyword, args))
+}
+
+/// 키워드 뒤의 값 전체 (구분자 공백 / '=' 제거, 따옴표 유지)
+fn raw_value(line: &str) -> &str {
+    let line = line.trim();
+    let end = line.find(|c: char| c.is_whitespace() || c == '=').unwrap_or(line.len());
+    let rest = line[end..].trim_start();
+    rest.strip_prefix('=').unwrap_or(rest).trim_start()
+}
+
+/// ~/ → 홈 디렉토리
+pub(crate) fn expand_tilde(path: &str) -> String {
+    match (path.strip_prefix("~/"), home::home_dir()) {
+        (Some(rest), Some(home)) => home.join(rest).to_string_lossy().to_string(),
+        _ if path == "~" => home::home_dir().map(|h| h.to_string_lossy().to_string()).unwrap_or_else(|| path.to_string()),
+        _ => path.to_string(),
+    }
+}
+
+/// OpenSSH 토큰 확장: %% %d %h %n %p %r %u, 그리고 선행 ~
+///
+/// %r (User 미지정) / %u에 로컬 사용자명이 필요한데 알 수 없으면 Error::Config
+fn expand_tokens(value: &str, alias: &str, host: &str, port: Option<u16>, user: Option<&str>) -> Result<String> {
+    let value = expand_tilde(value);
+    let mut out = String::with_capacity(value.len());
+    let mut chars = value.chars();
+    while let Some(ch) = chars.next() {
+        if ch != '%' {
+            out.push(ch);
+            continue;
+        }
+        match chars.next() {
+            Some('%') => out.push('%'),
+            Some('d') => out.push_str(&home::home_dir().map(|h| h.to_string_lossy().to_string()).unwrap_or_default()),
+            Some('h') => out.push_str(host),
+            Some('n') => out.push_str(alias),
+            Some('p') => out.push_str(&port.unwrap_or(22).to_string()),
+            Some('r') => match user {
+                Some(user) => out.push_str(user),
+                None => out.push_str(&local_username()?),
+            },
+            Some('u') => out.push_str(&local_username()?),
+            Some(other) => { out.push('%'); out.push(other); }
+            None => out.push('%'),
+        }
+    }
+    Ok(out)
+}
+
+/// OpenSSH 기본 키 중 존재하는 파일
+fn default_identity_files() -> Vec<PathBuf> {
+    let Some(home) = home::home_dir() else { return Vec::new() };
+    ["id_ed25519", "id_ecdsa", "id_rsa"].iter()
+        .map(|name| home.join(".ssh").join(name))
+        .filter(|p| p.is_file())
+        .collect()
+}
+
+/// 로컬 사용자명 (User 미지정 시 기본값, $USER / %USERNAME%)
+///
+/// 둘 다 없으면 Error::Config (임의의 사용자명으로 대신하지 않음)
+pub fn local_username() -> Result<String> {
+    std::env::var("USER")
+        .or_else(|_| std::env::var("USERNAME"))
+        .ok()
+        .filter(|user| !user.is_empty())
+        .ok_or_else(|| Error::Config("cannot determine the local user name ($USER is not set), specify User or user@host".to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 테스트마다 겹치지 않는 임시 디렉토리에 설정 파일들을 쓰고 첫 파일 경로 반환
+    fn write_config(name: &str, files: &[(&str, &str)]) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("minisftp-ssh-config-{}-{}", std::process::id(), name));
+        let _ = std::fs::remove_dir_all(&dir);
+        for (file, content) in files {
+            let path = dir.join(file);
+            std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+            std::fs::write(path, content).unwrap();
+        }
+        dir.join(files[0].0)
+    }
+
+    fn resolve_str(name: &str, content: &str, alias: &str) -> HostConfig {
+        let path = write_config(name, &[("config", content)]);
+        let resolved = resolve(&path, alias).unwrap();
+        std::fs::remove_dir_all(path.parent().unwrap()).unwrap();
+        resolved
+    }
+
+    #[test]
+    fn first_match_wins() {
+        let config = "\
+Host web
+    HostName web.internal
+    Port=2222
+    IdentityFile /keys/web
+Host * !db
+    HostName fallback
+    User deploy
+    Port 22
+    IdentityFile /keys/default
+";
+        let web = resolve_str("first-match", config, "web");
+        assert_eq!(web.host_name.as_deref(), Some("web.internal"));
+        assert_eq!(web.port, Some(2222));
+        assert_eq!(web.user.as_deref(), Some("deploy"));
+        // IdentityFile만 누적 (나온 순서대로)
+        assert_eq!(web.identity_files, [PathBuf::from("/keys/web"), PathBuf::from("/keys/default")]);
+
+        // !db → Host * 블록 전체 불일치
+        let db = resolve_str("first-match-db", config, "db");
+        assert_eq!(db.host_name.as_deref(), Some("db"));
+        assert_eq!(db.user, None);
+    }
+
+    #[test]
+    fn include_relative_and_depth_limit() {
+        let path = write_config("include", &[
+            ("config", "Include conf.d/*.conf\nHost web\n    User late\n"),
+            ("conf.d/b.conf", "Host web\n    Port 2200\n"),
+            ("conf.d/a.conf", "Host web\n    User early\n"),
+            // 자기 자신을 Include → 깊이 제한에서 멈춤
+            ("conf.d/loop.conf", "Include conf.d/loop.conf\nHost web\n    HostName looped\n"),
+        ]);
+        let web = resolve(&path, "web").unwrap();
+        assert_eq!(web.user.as_deref(), Some("early"));
+        assert_eq!(web.port, Some(2200));
+        assert_eq!(web.host_name.as_deref(), Some("looped"));
+        std::fs::remove_dir_all(path.parent().unwrap()).unwrap();
+    }
+
+    #[test]
+    fn match_block_skipped() {
+        let config = "\
+Match host web
+    User matched
+    Port 1
+Host web
+    User hosted
+";
+        let web = resolve_str("match", config, "web");
+        assert_eq!(web.user.as_deref(), Some("hosted"));
+        assert_eq!(web.port, None);
+    }
+
+    #[test]
+    fn proxy_first_seen() {
+        let config = "\
+Host cmd
+    ProxyCommand nc -X connect -x proxy:8080 %h %p
+    ProxyJump bastion
+Host none
+    ProxyJump none
+Host *
+    ProxyJump admin@bastion:2200
+    ProxyCommand ssh -W %h:%p gw
+Host bastion
+    HostName bastion.example.com
+    User ops
+";
+        let cmd = resolve_str("proxy-cmd", config, "cmd");
+        assert_eq!(cmd.proxy_command.as_deref(), Some("nc -X connect -x proxy:8080 %h %p"));
+        assert_eq!(cmd.proxy_jump, None);
+
+        // none도 첫 값 → 이후 Host * 의 ProxyJump / ProxyCommand 무시
+        let none = resolve_str("proxy-none", config, "none");
+        assert_eq!((none.proxy_jump, none.proxy_command), (None, None));
+
+        let web = resolve_str("proxy-jump", config, "web");
+        assert_eq!(web.proxy_jump.as_deref(), Some("admin@bastion:2200"));
+        assert_eq!(web.proxy_command, None);
+        // hop도 같은 파일의 별칭으로 해석, 명시한 user / port가 우선
+        assert_eq!(web.jump_hosts.len(), 1);
+        assert_eq!(web.jump_hosts[0].host, "bastion.example.com");
+        assert_eq!(web.jump_hosts[0].port, 2200);
+        assert_eq!(web.jump_hosts[0].username, "admin");
+    }
+
+    #[test]
+    fn token_expansion() {
+        let config = "\
+Host web
+    HostName %h.example.com
+    User deploy
+    Port 2200
+    IdentityFile /keys/%r@%h:%p
+    UserKnownHostsFile /known/%n_%%
+";
+        let web = resolve_str("tokens", config, "web");
+        assert_eq!(web.host_name.as_deref(), Some("web.example.com"));
+        assert_eq!(web.identity_files, [PathBuf::from("/keys/deploy@web.example.com:2200")]);
+        assert_eq!(web.user_known_hosts_file, Some(PathBuf::from("/known/web_%")));
+
+        let config = web.to_connect_config("web").unwrap();
+        assert_eq!((config.host.as_str(), config.port, config.username.as_str()), ("web.example.com", 2200, "deploy"));
+    }
+
+    #[test]
+    fn split_line_forms() {
+        assert_eq!(split_line("  # comment"), None);
+        assert_eq!(split_line("Port=22"), Some(("port".to_string(), vec!["22".to_string()])));
+        assert_eq!(split_line("HostName = a.example.com # note"), Some(("hostname".to_string(), vec!["a.example.com".to_string()])));
+        assert_eq!(
+            split_line(r#"IdentityFile "/keys/my key""#),
+            Some(("identityfile".to_string(), vec!["/keys/my key".to_string()])),
+        );
+        assert_eq!(split_host_port("[::1]:2222"), ("::1", Some(2222)));
+        assert_eq!(split_host_port("::1"), ("::1", None));
+    }
+}
@@ -5,25 +5,47 @@
 // can_transition_to()로 허용된 전이만 가능하게 강제
 //
 // 상태 흐름:
-//   Idle → TcpConnecting → VersionExchange → KeyExchange
-//     → Encrypted → Authenticating → Authenticated
+//   Idle → TcpConnecting → VersionExchange → KeyExchange → Encrypted
+//     → Authenticating → Authenticated
 //     → ChannelOpening → SftpReady → Disconnecting → Disconnected
 //
+//   핸드셰이크 단계는 실제 프로토콜 이벤트 시점에 전이
+//   - TCP(프록시 / 점프 경로) 연결 완료, 클라이언트 식별 문자열 전송 → VersionExchange
+//   - 서버 식별 문자열 수신                                      → KeyExchange { server_id }
+//   - 키 교환 완료 (NEWKEYS)                                     → Encrypted { kex, cipher, 호스트키 }
+//
 //   어느 상태에서든 → Disconnecting, Error 전이 가능
+//
+//   재연결: Error / Disconnected(서버 종료) → Reconnecting → TcpConnecting → ...
+//           실패 시 Error → 다음 시도 Reconnecting
 
 #[derive(Debug, Clone, PartialEq)]
 pub enum ConnectionState {
     Idle,
     TcpConnecting,
     VersionExchange,
-    KeyExchange,
-    Encrypted,
+    /// 서버 식별 문자열 수신, 키 교환 진행 중 (server_id: "SSH-2.0-OpenSSH_9.6")
+    KeyExchange {
+        server_id: String,
+    },
+    /// 키 교환 완료, 이후 패킷은 암호화됨
+    /// (kex / cipher: 협상된 알고리즘, host_key_type: "ssh-ed25519" 등, fingerprint: "SHA256:...")
+    Encrypted {
+        kex: String,
+        cipher: String,
+        host_key_type: String,
+        fingerprint: String,
+    },
     Authenticating,
     Authenticated,
     ChannelOpening,
     SftpReady,
     Disconnecting,
     Disconnected,
+    /// 끊긴 연결 재수립 중 (attempt: 1부터)
+    Reconnecting {
+        attempt: u32,
+    },
     Error {
         state: Box<ConnectionState>,  // 에러 발생 시점의 상태
         message: String,
@@ -38,15 +60,18 @@ impl ConnectionState {
             (self, next),
             (Idle, TcpConnecting)
             | (TcpConnecting, VersionExchange)
-            | (VersionExchange, KeyExchange)
-            | (KeyExchange, Encrypted)
-            | (Encrypted, Authenticating)
+            | (VersionExchange, KeyExchange { .. })
+            | (KeyExchange { .. }, Encrypted { .. })
+            | (Encrypted { .. }, Authenticating)
             | (Authenticating, Authenticated)
             | (Authenticated, ChannelOpening)
             | (ChannelOpening, SftpReady)
             | (_, Disconnecting)
             | (Disconnecting, Disconnected)
             | (_, Error { .. })
+            | (Error { .. }, Reconnecting { .. })
+            | (Disconnected, Reconnecting { .. })
+            | (Reconnecting { .. }, TcpConnecting)
         )
     }
 }
@@ -58,4 +83,14 @@ impl ConnectionState {
 /// 동일한 trait을 구현하면 UI 교체 가능
 pub trait ConnectionObserver: Send + Sync {
     fn on_state_changed(&self, prev: &ConnectionState, next: &ConnectionState);
+
+    /// 점프 호스트 단계별 진행 (hop: 1부터, total: 점프 호스트 수, addr: "host:port")
+    /// state: TcpConnecting → VersionExchange → KeyExchange → Encrypted → Authenticating → Authenticated,
+    /// 실패 시 Error
+    /// 점프 중 메인 상태는 TcpConnecting 유지 (대상 서버로의 전송 경로 수립 단계)
+    fn on_hop_state_changed(&self, _hop: usize, _total: usize, _addr: &str, _state: &ConnectionState) {}
+
+    /// 인증 중 서버 배너 (SSH_MSG_USERAUTH_BANNER, 법적 고지 등 — 사용자에게 그대로 표시)
+    /// addr: 배너를 보낸 서버 "host:port" (점프 호스트 포함)
+    fn on_banner(&self, _addr: &str, _banner: &str) {}
 }
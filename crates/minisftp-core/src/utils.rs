@@ -9,8 +9,11 @@
 // - local_ls        : 로컬 디렉토리 목록 (플랫폼 독립적)
 // - resolve_path    : 리모트 상대경로 → 절대경로
 // - resolve_local_path : 로컬 상대경로 → 절대경로 (OS 구분자 처리)
+// - wildcard_match  : OpenSSH 스타일 패턴 매칭 (*, ?)
+// - config_dir      : miniSFTP 설정 디렉토리 (플랫폼별)
 
 use chrono::{DateTime, Datelike, Local, LocalResult, TimeZone, Timelike, Utc};
+use crate::sftp::FileType;
 
 // ── 포맷 유틸 ────────────────────────────────────────────────────────────────
 
@@ -83,7 +86,7 @@ pub fn fmt_size(bytes: u64) -> String {
 ///
 /// [=================>    ] 75%  3.2MB / 4.3MB  1.2MB/s
 pub fn print_progress(transferred: u64, total: u64, elapsed_secs: f64) {
-    let percent = if total > 0 { transferred * 100 / total } else { 0 };
+    let percent = (transferred * 100).checked_div(total).unwrap_or(0);
     let speed   = if elapsed_secs > 0.0 { transferred as f64 / elapsed_secs } else { 0.0 };
 
     let filled = (percent as usize * 20 / 100).min(20);
@@ -112,13 +115,23 @@ pub fn local_ls(path: &str) -> std::io::Result<Vec<crate::sftp::FileEntry>> {
         .filter_map(|res| res.ok())
         .map(|entry| {
             let name   = entry.file_name().to_string_lossy().to_string();
+            // DirEntry::metadata는 링크를 따라가지 않음 → 링크면 대상 경로 / 종류를 따로 확인
             let meta   = entry.metadata().ok();
-            let is_dir = meta.as_ref().map(|m| m.is_dir()).unwrap_or(false);
+            let file_type = meta.as_ref().map(|m| local_file_type(&m.file_type())).unwrap_or(FileType::Unknown);
+            let (target_type, link_target) = if file_type.is_symlink() {
+                (
+                    std::fs::metadata(entry.path()).ok().map(|m| local_file_type(&m.file_type())),
+                    std::fs::read_link(entry.path()).ok().map(|t| t.to_string_lossy().to_string()),
+                )
+            } else {
+                (None, None)
+            };
             let size   = meta.as_ref().map(|m| m.len()).unwrap_or(0);
-            let mtime  = meta.as_ref()
-                .and_then(|m| m.modified().ok())
+            let unix_secs = |t: std::io::Result<std::time::SystemTime>| t.ok()
                 .and_then(|t| t.duration_since(std::time::UNIX_EPOCH).ok())
                 .map(|d| d.as_secs());
+            let atime  = meta.as_ref().and_then(|m| unix_secs(m.accessed()));
+            let mtime  = meta.as_ref().and_then(|m| unix_secs(m.modified()));
 
             // unix permission bits는 unix 전용 trait으로만 접근 가능
             // Windows에서는 None → permission_str이 "d?????????" / "----------" 반환
@@ -133,14 +146,33 @@ pub fn local_ls(path: &str) -> std::io::Result<Vec<crate::sftp::FileEntry>> {
             #[cfg(not(unix))]
             let (permissions, uid, gid): (Option<u32>, Option<u32>, Option<u32>) = (None, None, None);
 
-            crate::sftp::FileEntry { name, is_dir, size, permissions, uid, gid, mtime }
+            crate::sftp::FileEntry {
+                name, file_type, target_type, link_target, size, permissions, uid, gid, atime, mtime,
+                extended: Vec::new(),
+            }
         })
         .collect();
 
-    entries.sort_by(|a, b| b.is_dir.cmp(&a.is_dir).then(a.name.cmp(&b.name)));
+    entries.sort_by(|a, b| b.is_dir().cmp(&a.is_dir()).then(a.name.cmp(&b.name)));
     Ok(entries)
 }
 
+/// std 파일 종류 → FileType (장치 / FIFO / 소켓은 unix에서만 구분)
+fn local_file_type(t: &std::fs::FileType) -> FileType {
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::FileTypeExt;
+        if t.is_block_device() { return FileType::BlockDevice; }
+        if t.is_char_device()  { return FileType::CharDevice; }
+        if t.is_fifo()         { return FileType::Fifo; }
+        if t.is_socket()       { return FileType::Socket; }
+    }
+    if t.is_symlink()   { FileType::Symlink }
+    else if t.is_dir()  { FileType::Dir }
+    else if t.is_file() { FileType::File }
+    else                { FileType::Unknown }
+}
+
 /// 리모트 상대경로 → 절대경로 변환
 ///
 /// - 절대경로 입력 → 그대로 반환
@@ -176,3 +208,52 @@ pub fn resolve_local_path(current: &str, path: &str) -> String {
         Path::new(current).join(p).to_string_lossy().to_string()
     }
 }
+
+/// OpenSSH 스타일 와일드카드 매칭 (`*`: 0개 이상, `?`: 정확히 1개)
+///
+/// known_hosts 호스트 패턴, ssh_config Host 패턴에서 사용
+pub fn wildcard_match(pattern: &str, text: &str) -> bool {
+    let p: Vec<char> = pattern.chars().collect();
+    let t: Vec<char> = text.chars().collect();
+
+    // 마지막 '*' 위치로 백트래킹하는 greedy 매칭
+    let (mut pi, mut ti) = (0, 0);
+    let mut star: Option<(usize, usize)> = None;
+    while ti < t.len() {
+        if pi < p.len() && (p[pi] == '?' || p[pi] == t[ti]) {
+            pi += 1;
+            ti += 1;
+        } else if pi < p.len() && p[pi] == '*' {
+            star = Some((pi, ti));
+            pi += 1;
+        } else if let Some((sp, st)) = star {
+            pi = sp + 1;
+            ti = st + 1;
+            star = Some((sp, st + 1));
+        } else {
+            return false;
+        }
+    }
+    p[pi..].iter().all(|&c| c == '*')
+}
+
+// ── 설정 디렉토리 ─────────────────────────────────────────────────────────────
+
+/// miniSFTP 설정 디렉토리 (프로필 등 저장 위치)
+///
+/// - Windows : %APPDATA%\minisftp
+/// - macOS   : ~/Library/Application Support/minisftp
+/// - 그 외    : $XDG_CONFIG_HOME/minisftp (없으면 ~/.config/minisftp)
+pub fn config_dir() -> Option<std::path::PathBuf> {
+    let base = if cfg!(windows) {
+        std::env::var_os("APPDATA").map(std::path::PathBuf::from)
+    } else if cfg!(target_os = "macos") {
+        home::home_dir().map(|h| h.join("Library").join("Application Support"))
+    } else {
+        std::env::var_os("XDG_CONFIG_HOME")
+            .map(std::path::PathBuf::from)
+            .filter(|p| p.is_absolute())
+            .or_else(|| home::home_dir().map(|h| h.join(".config")))
+    };
+    base.map(|b| b.join("minisftp"))
+}
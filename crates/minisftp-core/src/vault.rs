@@ -0,0 +1,552 @@
+// miniSFTP Credential Vault (자격 증명 볼트)
+// author: kodeholic (powered by Claude)
+//
+// 프로필이 참조하는 패스워드 / key passphrase를 마스터 패스워드로 암호화해서 저장
+//
+// - 키 유도: Argon2id (메모리 하드, 파라미터는 파일에 기록 → 나중에 올려도 기존 볼트 열림)
+// - 암호화: XChaCha20-Poly1305 (저장할 때마다 새 nonce, 헤더를 AAD로 묶어 파라미터 변조도 검출)
+// - 저장 위치: utils::config_dir()/vault (unix는 0600)
+// - 잠금 해제 후 키와 항목은 메모리에만 존재 (Zeroizing / Secret → lock / drop 시 소거)
+// - 유휴 타임아웃: 마지막 사용 후 timeout이 지나면 expire_if_idle()이 키와 항목을 소거 → Error::VaultLocked
+//   백그라운드 타이머 없음 → 볼트를 들고 있는 쪽(CLI 입력 대기 후, GUI 주기 타이머)이 expire_if_idle() 호출
+//   항목 접근(get / ids / set / remove)도 먼저 expire_if_idle() 적용
+//
+// 파일 형식 (텍스트, 항목 목록은 data 안에만 있음):
+//   # miniSFTP credential vault
+//   version 1
+//   kdf argon2id 19456 2 1        (m_cost KiB, t_cost, p_cost)
+//   salt <base64>
+//   nonce <base64>
+//   data <base64>                 (암호문 + 태그)
+//
+// 항목 id: Profile::secret_id() ("prod-web/password", "prod-web/passphrase:~/.ssh/id_ed25519")
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+use argon2::{Algorithm, Argon2, Params, Version};
+use chacha20poly1305::aead::rand_core::RngCore;
+use chacha20poly1305::aead::{Aead, AeadCore, KeyInit, OsRng, Payload};
+use chacha20poly1305::{Key, XChaCha20Poly1305, XNonce};
+use data_encoding::BASE64;
+
+use crate::error::{Error, Result};
+use crate::secret::{Secret, Zeroizing};
+use crate::utils::config_dir;
+
+const VERSION: u32 = 1;
+const KEY_LEN: usize = 32;
+const SALT_LEN: usize = 16;
+const NONCE_LEN: usize = 24;
+
+/// 기본 유휴 타임아웃
+pub const DEFAULT_TIMEOUT: Duration = Duration::from_secs(15 * 60);
+
+/// Argon2id 파라미터 (OWASP 권장 최소값: 19 MiB, 2회, 병렬 1)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct KdfParams {
+    /// 메모리 (KiB)
+    pub m_cost: u32,
+    pub t_cost: u32,
+    pub p_cost: u32,
+}
+
+impl Default for KdfParams {
+    fn default() -> Self {
+        Self { m_cost: 19 * 1024, t_cost: 2, p_cost: 1 }
+    }
+}
+
+/// 잠금 해제된 상태 (키 + 복호화된 항목)
+struct Unlocked {
+    kdf: KdfParams,
+    salt: Vec<u8>,
+    key: Zeroizing<[u8; KEY_LEN]>,
+    entries: BTreeMap<String, Secret>,
+    last_used: Instant,
+}
+
+/// 암호화된 자격 증명 저장소
+///
+/// 잠금 해제 전에는 파일 경로만 가짐, 항목 조회 / 변경은 unlock() 후에만 가능
+pub struct Vault {
+    path: PathBuf,
+    timeout: Option<Duration>,
+    unlocked: Option<Unlocked>,
+}
+
+impl Vault {
+    /// 기본 저장 위치 (config_dir()/vault)
+    pub fn default_path() -> Option<PathBuf> {
+        config_dir().map(|dir| dir.join("vault"))
+    }
+
+    /// 새 볼트 생성 (이미 있으면 에러) → 잠금 해제된 상태로 반환
+    pub fn create(path: impl Into<PathBuf>, master: &Secret, kdf: KdfParams) -> Result<Self> {
+        let path = path.into();
+        if path.exists() {
+            return Err(Error::Vault(format!("{} already exists", path.display())));
+        }
+        if master.is_empty() {
+            return Err(Error::Vault("master password is empty".to_string()));
+        }
+
+        let salt = random_bytes(SALT_LEN);
+        let key = derive_key(master, &salt, kdf)?;
+        let mut vault = Self { path, timeout: Some(DEFAULT_TIMEOUT), unlocked: None };
+        vault.unlocked = Some(Unlocked { kdf, salt, key, entries: BTreeMap::new(), last_used: Instant::now() });
+        vault.save()?;
+        Ok(vault)
+    }
+
+    /// 기존 볼트 열기 (잠긴 상태, 파일 형식만 확인)
+    pub fn open(path: impl Into<PathBuf>) -> Result<Self> {
+        let path = path.into();
+        read_file(&path)?;
+        Ok(Self { path, timeout: Some(DEFAULT_TIMEOUT), unlocked: None })
+    }
+
+    /// 기본 위치의 볼트 열기
+    pub fn open_default() -> Result<Self> {
+        let path = Self::default_path()
+            .ok_or_else(|| Error::Vault("cannot locate config directory".to_string()))?;
+        Self::open(path)
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// 유휴 타임아웃 설정 (None이면 lock() 전까지 유지)
+    pub fn set_timeout(&mut self, timeout: Option<Duration>) {
+        self.timeout = timeout;
+    }
+
+    pub fn timeout(&self) -> Option<Duration> {
+        self.timeout
+    }
+
+    /// 마스터 패스워드로 잠금 해제 (파일을 다시 읽으므로 다른 프로세스의 변경도 반영)
+    ///
+    /// 패스워드가 틀리거나 파일이 변조됐으면 Error::Vault
+    pub fn unlock(&mut self, master: &Secret) -> Result<()> {
+        self.lock();
+        let file = read_file(&self.path)?;
+        let key = derive_key(master, &file.salt, file.kdf)?;
+        let entries = decrypt(&key, &file)?;
+        self.unlocked = Some(Unlocked {
+            kdf: file.kdf,
+            salt: file.salt,
+            key,
+            entries,
+            last_used: Instant::now(),
+        });
+        tracing::debug!("[vault] unlocked {}", self.path.display());
+        Ok(())
+    }
+
+    /// 잠금 (키와 항목을 메모리에서 소거)
+    pub fn lock(&mut self) {
+        if self.unlocked.take().is_some() {
+            tracing::debug!("[vault] locked");
+        }
+    }
+
+    /// 유휴 타임아웃이 지났으면 잠금 (키와 항목 소거), 잠갔으면 true
+    ///
+    /// 접근이 없는 동안에는 자동으로 소거되지 않음 → 볼트를 오래 들고 있는 쪽에서 주기적으로 호출
+    pub fn expire_if_idle(&mut self) -> bool {
+        if self.expires_in() != Some(Duration::ZERO) {
+            return false;
+        }
+        tracing::debug!("[vault] idle timeout");
+        self.lock();
+        true
+    }
+
+    /// 잠금 해제 상태인지 (유휴 타임아웃이 지났으면 false)
+    pub fn is_unlocked(&self) -> bool {
+        self.expires_in() != Some(Duration::ZERO) && self.unlocked.is_some()
+    }
+
+    /// 자동 잠금까지 남은 시간 (잠겨 있거나 타임아웃이 없으면 None)
+    pub fn expires_in(&self) -> Option<Duration> {
+        let unlocked = self.unlocked.as_ref()?;
+        let timeout = self.timeout?;
+        Some(timeout.saturating_sub(unlocked.last_used.elapsed()))
+    }
+
+    /// 항목 조회 (없으면 None, 잠겨 있으면 Error::VaultLocked)
+    pub fn get(&mut self, id: &str) -> Result<Option<Secret>> {
+        Ok(self.unlocked()?.entries.get(id).cloned())
+    }
+
+    /// 항목 id 목록 (정렬)
+    pub fn ids(&mut self) -> Result<Vec<String>> {
+        Ok(self.unlocked()?.entries.keys().cloned().collect())
+    }
+
+    /// 항목 저장 후 파일에 기록
+    pub fn set(&mut self, id: &str, secret: Secret) -> Result<()> {
+        if id.is_empty() {
+            return Err(Error::Vault("secret id is empty".to_string()));
+        }
+        self.unlocked()?.entries.insert(id.to_string(), secret);
+        self.save()
+    }
+
+    /// 항목 삭제 후 파일에 기록 (없었으면 false)
+    pub fn remove(&mut self, id: &str) -> Result<bool> {
+        let removed = self.unlocked()?.entries.remove(id).is_some();
+        if removed {
+            self.save()?;
+        }
+        Ok(removed)
+    }
+
+    /// 마스터 패스워드 변경 (현재 패스워드 확인 후 새 salt로 다시 암호화)
+    ///
+    /// 잠겨 있어도 사용 가능, 완료 후에는 잠금 해제 상태
+    pub fn change_master_password(&mut self, current: &Secret, new: &Secret) -> Result<()> {
+        if new.is_empty() {
+            return Err(Error::Vault("master password is empty".to_string()));
+        }
+        self.unlock(current)?;
+
+        // 새 salt / 키로 파일을 먼저 기록하고 성공한 뒤에만 교체 (실패하면 이전 패스워드 그대로)
+        let unlocked = self.unlocked.as_mut().expect("unlocked");
+        let salt = random_bytes(SALT_LEN);
+        let key = derive_key(new, &salt, unlocked.kdf)?;
+        unlocked.write(&self.path, &salt, &key)?;
+        unlocked.salt = salt;
+        unlocked.key = key;
+        tracing::info!("[vault] master password changed");
+        Ok(())
+    }
+
+    /// 잠금 해제 상태 확인 + 유휴 타임아웃 처리 (사용 시각 갱신)
+    fn unlocked(&mut self) -> Result<&mut Unlocked> {
+        self.expire_if_idle();
+        let unlocked = self.unlocked.as_mut().ok_or(Error::VaultLocked)?;
+        unlocked.last_used = Instant::now();
+        Ok(unlocked)
+    }
+
+    /// 현재 키로 파일에 기록
+    fn save(&self) -> Result<()> {
+        let unlocked = self.unlocked.as_ref().ok_or(Error::VaultLocked)?;
+        unlocked.write(&self.path, &unlocked.salt, &unlocked.key)
+    }
+}
+
+impl Unlocked {
+    /// 항목을 새 nonce로 암호화해서 기록 (임시 파일에 쓰고 fsync한 뒤 교체)
+    fn write(&self, path: &Path, salt: &[u8], key: &[u8; KEY_LEN]) -> Result<()> {
+        let mut plain = Zeroizing::new(Vec::new());
+        for (id, secret) in &self.entries {
+            for field in [id.as_bytes(), secret.expose().as_bytes()] {
+                plain.extend_from_slice(&(field.len() as u32).to_be_bytes());
+                plain.extend_from_slice(field);
+            }
+        }
+
+        let nonce = XChaCha20Poly1305::generate_nonce(&mut OsRng);
+        let aad = header_aad(self.kdf, salt);
+        let data = cipher(key)
+            .encrypt(&nonce, Payload { msg: &plain, aad: aad.as_bytes() })
+            .map_err(|_| Error::Vault("encryption failed".to_string()))?;
+
+        let content = format!(
+            "# miniSFTP credential vault\nversion {}\nkdf argon2id {} {} {}\nsalt {}\nnonce {}\ndata {}\n",
+            VERSION, self.kdf.m_cost, self.kdf.t_cost, self.kdf.p_cost,
+            BASE64.encode(salt), BASE64.encode(&nonce), BASE64.encode(&data),
+        );
+
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir)?;
+        }
+        let tmp = path.with_extension("tmp");
+        write_private(&tmp, content.as_bytes())?;
+        std::fs::rename(&tmp, path)?;
+        Ok(())
+    }
+}
+
+// ── 파일 / 암호화 ────────────────────────────────────────────────────────────
+
+/// 볼트 파일 내용 (암호문 상태)
+struct VaultFile {
+    kdf: KdfParams,
+    salt: Vec<u8>,
+    nonce: Vec<u8>,
+    data: Vec<u8>,
+}
+
+fn read_file(path: &Path) -> Result<VaultFile> {
+    let content = match std::fs::read_to_string(path) {
+        Ok(content) => content,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+            return Err(Error::Vault(format!("{} does not exist", path.display())));
+        }
+        Err(e) => return Err(e.into()),
+    };
+    parse_file(&content).map_err(|m| Error::Vault(format!("{}: {}", path.display(), m)))
+}
+
+fn parse_file(content: &str) -> std::result::Result<VaultFile, String> {
+    let (mut version, mut kdf, mut salt, mut nonce, mut data) = (None, None, None, None, None);
+    let base64 = |v: &str| BASE64.decode(v.as_bytes()).map_err(|_| format!("invalid base64: {}", v));
+
+    for line in content.lines().map(str::trim).filter(|l| !l.is_empty() && !l.starts_with('#')) {
+        let (key, value) = line.split_once(' ').unwrap_or((line, ""));
+        match key {
+            "version" => version = value.parse::<u32>().ok(),
+            "kdf" => {
+                let fields: Vec<&str> = value.split_whitespace().collect();
+                let [alg, m, t, p] = fields[..] else {
+                    return Err(format!("invalid kdf line: {}", value));
+                };
+                if alg != "argon2id" {
+                    return Err(format!("unsupported kdf: {}", alg));
+                }
+                let num = |s: &str| s.parse::<u32>().map_err(|_| format!("invalid kdf parameter: {}", s));
+                kdf = Some(KdfParams { m_cost: num(m)?, t_cost: num(t)?, p_cost: num(p)? });
+            }
+            "salt"  => salt = Some(base64(value)?),
+            "nonce" => nonce = Some(base64(value)?),
+            "data"  => data = Some(base64(value)?),
+            _ => return Err(format!("unknown field: {}", key)),
+        }
+    }
+
+    match version {
+        Some(VERSION) => {}
+        Some(v) => return Err(format!("unsupported vault version {}", v)),
+        None => return Err("not a miniSFTP vault (no version)".to_string()),
+    }
+    let missing = |field: &str| format!("missing {}", field);
+    let nonce = nonce.ok_or_else(|| missing("nonce"))?;
+    if nonce.len() != NONCE_LEN {
+        return Err("invalid nonce length".to_string());
+    }
+    Ok(VaultFile {
+        kdf: kdf.ok_or_else(|| missing("kdf"))?,
+        salt: salt.ok_or_else(|| missing("salt"))?,
+        nonce,
+        data: data.ok_or_else(|| missing("data"))?,
+    })
+}
+
+/// 복호화 + 항목 목록 복원 (태그 불일치 = 잘못된 패스워드 또는 변조)
+fn decrypt(key: &[u8; KEY_LEN], file: &VaultFile) -> Result<BTreeMap<String, Secret>> {
+    let aad = header_aad(file.kdf, &file.salt);
+    let plain = Zeroizing::new(
+        cipher(key)
+            .decrypt(XNonce::from_slice(&file.nonce), Payload { msg: &file.data, aad: aad.as_bytes() })
+            .map_err(|_| Error::Vault("wrong master password or corrupted vault".to_string()))?,
+    );
+
+    let corrupted = || Error::Vault("corrupted vault entries".to_string());
+    let mut entries = BTreeMap::new();
+    let mut rest = &plain[..];
+    while !rest.is_empty() {
+        let mut fields = [String::new(), String::new()];
+        for field in fields.iter_mut() {
+            let (len, tail) = rest.split_first_chunk::<4>().ok_or_else(corrupted)?;
+            let len = u32::from_be_bytes(*len) as usize;
+            if tail.len() < len {
+                return Err(corrupted());
+            }
+            *field = String::from_utf8(tail[..len].to_vec()).map_err(|_| corrupted())?;
+            rest = &tail[len..];
+        }
+        let [id, secret] = fields;
+        entries.insert(id, Secret::from(secret));
+    }
+    Ok(entries)
+}
+
+/// 암호문에 묶는 헤더 (버전 / KDF 파라미터 / salt를 바꾸면 복호화 실패)
+fn header_aad(kdf: KdfParams, salt: &[u8]) -> String {
+    format!("minisftp-vault {} argon2id {} {} {} {}", VERSION, kdf.m_cost, kdf.t_cost, kdf.p_cost, BASE64.encode(salt))
+}
+
+fn derive_key(master: &Secret, salt: &[u8], kdf: KdfParams) -> Result<Zeroizing<[u8; KEY_LEN]>> {
+    let params = Params::new(kdf.m_cost, kdf.t_cost, kdf.p_cost, Some(KEY_LEN))
+        .map_err(|e| Error::Vault(format!("invalid kdf parameters: {}", e)))?;
+    let mut key = Zeroizing::new([0u8; KEY_LEN]);
+    Argon2::new(Algorithm::Argon2id, Version::V0x13, params)
+        .hash_password_into(master.expose().as_bytes(), salt, key.as_mut())
+        .map_err(|e| Error::Vault(format!("key derivation failed: {}", e)))?;
+    Ok(key)
+}
+
+fn cipher(key: &[u8; KEY_LEN]) -> XChaCha20Poly1305 {
+    XChaCha20Poly1305::new(Key::from_slice(key))
+}
+
+fn random_bytes(len: usize) -> Vec<u8> {
+    let mut bytes = vec![0u8; len];
+    OsRng.fill_bytes(&mut bytes);
+    bytes
+}
+
+/// 소유자만 읽을 수 있게 생성 (unix: 0600), 디스크 기록까지 대기
+///
+/// rename 전에 fsync → 교체 직후 전원이 꺼져도 빈 파일 / 일부만 쓴 파일이 남지 않음
+fn write_private(path: &Path, content: &[u8]) -> std::io::Result<()> {
+    use std::io::Write;
+
+    let mut options = std::fs::OpenOptions::new();
+    options.write(true).create(true).truncate(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+    let mut file = options.open(path)?;
+    file.write_all(content)?;
+    file.sync_all()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 테스트용 최소 파라미터 (Argon2 최소값: 8 KiB, 1회)
+    const FAST: KdfParams = KdfParams { m_cost: 8, t_cost: 1, p_cost: 1 };
+
+    /// 테스트마다 겹치지 않는 임시 디렉토리 (끝나면 remove_dir_all)
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("minisftp-vault-{}-{}", std::process::id(), name));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    fn secret(s: &str) -> Secret {
+        Secret::from(s.to_string())
+    }
+
+    fn is_vault_error(result: Result<()>) -> bool {
+        matches!(result, Err(Error::Vault(_)))
+    }
+
+    #[test]
+    fn create_unlock_round_trip() {
+        let dir = temp_dir("round-trip");
+        let path = dir.join("vault");
+        let mut vault = Vault::create(&path, &secret("master"), FAST).unwrap();
+        vault.set("prod/password", secret("s3cret")).unwrap();
+        vault.set("prod/passphrase:~/.ssh/id_ed25519", secret("")).unwrap();
+        drop(vault);
+
+        let mut vault = Vault::open(&path).unwrap();
+        assert!(matches!(vault.get("prod/password"), Err(Error::VaultLocked)));
+        vault.unlock(&secret("master")).unwrap();
+        assert_eq!(vault.get("prod/password").unwrap().unwrap().expose(), "s3cret");
+        assert_eq!(vault.ids().unwrap(), ["prod/passphrase:~/.ssh/id_ed25519", "prod/password"]);
+        assert!(vault.remove("prod/password").unwrap());
+        assert!(vault.get("prod/password").unwrap().is_none());
+
+        assert!(Vault::create(&path, &secret("other"), FAST).is_err());
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn wrong_password() {
+        let dir = temp_dir("wrong-password");
+        let path = dir.join("vault");
+        Vault::create(&path, &secret("master"), FAST).unwrap();
+
+        let mut vault = Vault::open(&path).unwrap();
+        assert!(is_vault_error(vault.unlock(&secret("Master"))));
+        assert!(!vault.is_unlocked());
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn tampered_file_rejected() {
+        let dir = temp_dir("tampered");
+        let path = dir.join("vault");
+        let mut vault = Vault::create(&path, &secret("master"), FAST).unwrap();
+        vault.set("id", secret("value")).unwrap();
+        let content = std::fs::read_to_string(&path).unwrap();
+        let file = parse_file(&content).unwrap();
+        let key = derive_key(&secret("master"), &file.salt, file.kdf).unwrap();
+        assert_eq!(decrypt(&key, &file).unwrap()["id"].expose(), "value");
+
+        // 같은 키라도 헤더(KDF 파라미터)가 바뀌면 AAD 불일치
+        let header = VaultFile { kdf: KdfParams { t_cost: 2, ..file.kdf }, ..parse_file(&content).unwrap() };
+        assert!(matches!(decrypt(&key, &header), Err(Error::Vault(_))));
+
+        // 암호문 1바이트 변조 → 태그 불일치
+        let mut data = parse_file(&content).unwrap();
+        data.data[0] ^= 1;
+        assert!(matches!(decrypt(&key, &data), Err(Error::Vault(_))));
+
+        // 파일의 헤더 변조도 unlock에서 거부
+        std::fs::write(&path, content.replace("kdf argon2id 8 1 1", "kdf argon2id 8 2 1")).unwrap();
+        assert!(is_vault_error(Vault::open(&path).unwrap().unlock(&secret("master"))));
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn change_master_password_then_reopen() {
+        let dir = temp_dir("change-master");
+        let path = dir.join("vault");
+        let mut vault = Vault::create(&path, &secret("old"), FAST).unwrap();
+        vault.set("id", secret("value")).unwrap();
+        vault.lock();
+
+        assert!(is_vault_error(vault.change_master_password(&secret("wrong"), &secret("new"))));
+        vault.change_master_password(&secret("old"), &secret("new")).unwrap();
+        assert!(vault.is_unlocked());
+
+        let mut reopened = Vault::open(&path).unwrap();
+        assert!(is_vault_error(reopened.unlock(&secret("old"))));
+        reopened.unlock(&secret("new")).unwrap();
+        assert_eq!(reopened.get("id").unwrap().unwrap().expose(), "value");
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn change_master_password_keeps_old_key_on_write_failure() {
+        let dir = temp_dir("change-master-fail");
+        let path = dir.join("vault");
+        let mut vault = Vault::create(&path, &secret("old"), FAST).unwrap();
+
+        // 임시 파일 자리에 디렉토리 → 기록 실패
+        std::fs::create_dir(path.with_extension("tmp")).unwrap();
+        assert!(vault.change_master_password(&secret("old"), &secret("new")).is_err());
+        std::fs::remove_dir(path.with_extension("tmp")).unwrap();
+
+        // 메모리의 키도 이전 패스워드 그대로 → 이후 저장한 파일도 old로 열림
+        vault.set("id", secret("value")).unwrap();
+        let mut reopened = Vault::open(&path).unwrap();
+        reopened.unlock(&secret("old")).unwrap();
+        assert_eq!(reopened.get("id").unwrap().unwrap().expose(), "value");
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn expire_if_idle_wipes_key() {
+        let dir = temp_dir("idle");
+        let path = dir.join("vault");
+        let mut vault = Vault::create(&path, &secret("master"), FAST).unwrap();
+
+        vault.set_timeout(None);
+        assert!(!vault.expire_if_idle());
+        assert!(vault.is_unlocked());
+
+        vault.set_timeout(Some(Duration::ZERO));
+        assert!(!vault.is_unlocked());
+        assert!(vault.expire_if_idle());
+        assert!(vault.unlocked.is_none());
+        assert!(matches!(vault.get("id"), Err(Error::VaultLocked)));
+        // 이미 잠겼으면 다시 소거할 것 없음
+        assert!(!vault.expire_if_idle());
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+}
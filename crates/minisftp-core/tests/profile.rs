@@ -0,0 +1,169 @@
+// miniSFTP Profile Tests
+// author: kodeholic (powered by Claude)
+//
+// profiles.toml 왕복 (TOML / JSON), FileZilla sitemanager.xml 가져오기 (tests/fixtures)
+
+use std::path::{Path, PathBuf};
+
+use minisftp_core::config::HostKeyPolicy;
+use minisftp_core::profile::{Profile, ProfileAuth, ProfileStore};
+
+/// 테스트마다 겹치지 않는 임시 파일 경로 (끝나면 remove_file)
+fn temp_path(name: &str) -> PathBuf {
+    std::env::temp_dir().join(format!("minisftp-test-{}-{}", std::process::id(), name))
+}
+
+fn fixture(name: &str) -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures").join(name)
+}
+
+fn full_profile() -> Profile {
+    Profile {
+        name: "prod-web".to_string(),
+        folder: Some("Production/Web".to_string()),
+        tags: vec!["prod".to_string(), "web".to_string()],
+        host: "10.0.0.5".to_string(),
+        port: Some(2222),
+        username: Some("deploy".to_string()),
+        auth: vec![
+            ProfileAuth::Agent(None),
+            ProfileAuth::Agent(Some("SHA256:abc".to_string())),
+            ProfileAuth::PublicKey(PathBuf::from("~/.ssh/id_ed25519")),
+            ProfileAuth::KeyboardInteractive,
+            ProfileAuth::Password,
+        ],
+        jump: Some("admin@bastion:2200,gw".to_string()),
+        proxy: Some("socks5://127.0.0.1:1080".to_string()),
+        proxy_command: Some("nc -X connect %h %p".to_string()),
+        host_key_policy: Some(HostKeyPolicy::AcceptNew),
+        remote_dir: Some("/var/www".to_string()),
+        local_dir: Some(PathBuf::from("/home/deploy/web")),
+        comment: Some("quote \" backslash \\ newline \n tab \t".to_string()),
+    }
+}
+
+/// 파일에 쓴 내용을 그대로 열기
+fn open_toml(name: &str, content: &str) -> minisftp_core::error::Result<ProfileStore> {
+    let path = temp_path(name);
+    std::fs::write(&path, content).unwrap();
+    let store = ProfileStore::open(&path);
+    std::fs::remove_file(&path).unwrap();
+    store
+}
+
+#[test]
+fn toml_round_trip() {
+    let path = temp_path("round-trip.toml");
+    let minimal = Profile { name: "min".to_string(), host: "example.com".to_string(), ..Default::default() };
+
+    let mut store = ProfileStore::open(&path).unwrap();
+    store.upsert(full_profile()).unwrap();
+    store.upsert(minimal.clone()).unwrap();
+    store.save().unwrap();
+
+    let content = std::fs::read_to_string(&path).unwrap();
+    let reopened = ProfileStore::open(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+
+    assert_eq!(reopened.profiles(), &[full_profile(), minimal]);
+    assert_eq!(content.matches("[[profile]]").count(), 2);
+    // 값이 없는 항목은 쓰지 않음
+    assert!(!content.contains("tags = []"));
+}
+
+#[test]
+fn json_round_trip() {
+    let json = serde_json::to_string(&full_profile()).unwrap();
+    assert!(json.contains(r#""auth":["agent","agent:SHA256:abc","publickey:~/.ssh/id_ed25519","keyboard-interactive","password"]"#));
+    assert!(json.contains(r#""host_key_policy":"accept-new""#));
+    let parsed: Profile = serde_json::from_str(&json).unwrap();
+    assert_eq!(parsed, full_profile());
+
+    // 빠진 필드는 기본값
+    let parsed: Profile = serde_json::from_str(r#"{"name":"a","host":"h"}"#).unwrap();
+    assert_eq!(parsed, Profile { name: "a".to_string(), host: "h".to_string(), ..Default::default() });
+}
+
+#[test]
+fn toml_accepts_full_syntax() {
+    let store = open_toml("syntax.toml", r#"
+# 주석
+[[profile]] # 테이블 뒤 주석
+name = 'literal\path'
+folder = "/Production/Web/"
+host = "10.0.0.5"
+port = 22
+tags = [
+    "prod",   # 여러 줄 배열
+    "web",
+]
+auth = ["agent", "password",]
+timeout = 1.5
+extra = { nested = true }
+
+[[profile]]
+name = "second"
+host = """
+db.internal"""
+"#).unwrap();
+
+    let profiles = store.profiles();
+    assert_eq!(profiles.len(), 2);
+    assert_eq!(profiles[0].name, r"literal\path");
+    assert_eq!(profiles[0].folder.as_deref(), Some("Production/Web"));
+    assert_eq!(profiles[0].tags, ["prod", "web"]);
+    assert_eq!(profiles[0].auth, [ProfileAuth::Agent(None), ProfileAuth::Password]);
+    assert_eq!(profiles[1].host, "db.internal");
+}
+
+#[test]
+fn toml_rejects_invalid_profiles() {
+    let err = |content: &str| open_toml("invalid.toml", content).unwrap_err().to_string();
+
+    assert!(err("[[profile]]\nname = \"a\"\nhost = \"h\"\nauth = [\"telnet\"]\n").contains("invalid auth method: telnet"));
+    assert!(err("[[profile]]\nname = \"a\"\nhost = \"h\"\nport = 70000\n").contains("port"));
+    assert!(err("[[profile]]\nname = \"a\"\n").contains("host is empty"));
+    assert!(err("[[profile]]\nname = \"a\"\nhost = \"h\"\nhost_key_policy = \"maybe\"\n").contains("host_key_policy"));
+}
+
+#[test]
+fn filezilla_import() {
+    let mut store = ProfileStore::open(temp_path("filezilla.toml")).unwrap();
+    let report = store.import_filezilla(&fixture("sitemanager.xml")).unwrap();
+
+    assert_eq!(report.imported, ["prod & web", "db", "prod & web-2"]);
+    assert_eq!(report.skipped, ["legacy ftp (not SFTP, protocol 0)"]);
+
+    let web = store.get("prod & web").unwrap();
+    assert_eq!(web.folder, None);
+    assert_eq!(web.host, "10.0.0.5");
+    assert_eq!(web.port, Some(2222));
+    assert_eq!(web.username.as_deref(), Some("deploy"));
+    assert_eq!(web.auth, [ProfileAuth::Password]);
+    assert_eq!(web.comment.as_deref(), Some("main <web> server"));
+    assert_eq!(web.local_dir, Some(PathBuf::from(r"C:\work\web")));
+    assert_eq!(web.remote_dir.as_deref(), Some("/var/www"));
+
+    let db = store.get("db").unwrap();
+    assert_eq!(db.folder.as_deref(), Some("Production"));
+    assert_eq!(db.auth, [ProfileAuth::PublicKey(PathBuf::from("/home/dba/.ssh/id_ed25519"))]);
+
+    let nested = store.get("prod & web-2").unwrap();
+    assert_eq!(nested.folder.as_deref(), Some("Production/Web"));
+    assert_eq!(nested.auth, [ProfileAuth::KeyboardInteractive]);
+    assert_eq!(store.folders(), ["Production", "Production/Web"]);
+}
+
+#[test]
+fn filezilla_rejects_other_xml() {
+    let path = temp_path("not-filezilla.xml");
+    std::fs::write(&path, "<?xml version=\"1.0\"?><Settings><Item/></Settings>").unwrap();
+    let mut store = ProfileStore::open(temp_path("unused.toml")).unwrap();
+    let err = store.import_filezilla(&path).unwrap_err().to_string();
+    std::fs::write(&path, "<FileZilla3><Servers></FileZilla3>").unwrap();
+    let malformed = store.import_filezilla(&path).unwrap_err().to_string();
+    std::fs::remove_file(&path).unwrap();
+
+    assert!(err.contains("no <Servers> element"));
+    assert!(malformed.contains("not-filezilla.xml"));
+}